@@ -0,0 +1,387 @@
+use crate::resource::{
+    resourcelink::{self, Link, LinkKind},
+    rule::{
+        self, Action, ActionRequestMethod, Address, Condition, ConditionValue, GroupAttribute,
+        InvalidConditionError, SensorAttribute,
+    },
+    sensor,
+};
+use crate::{Bridge, Result};
+use chrono::Duration;
+use derive_setters::Setters;
+use serde_json::{json, Value as JsonValue};
+use thiserror::Error as ThisError;
+
+/// Status of the memory sensor when the lights were not turned on by the automation.
+const STATUS_IDLE: i32 = 0;
+/// Status of the memory sensor when the lights were turned on by the automation.
+const STATUS_ON: i32 = 1;
+/// Status of the memory sensor when the lights were dimmed by the automation.
+const STATUS_DIMMED: i32 = 2;
+
+/// Automation that turns on a group when motion is detected.
+///
+/// The automation is compiled into a `CLIPGenericStatus` sensor that stores the state of the
+/// automation, the rules that implement it and a resourcelink that groups all resources.
+///
+/// - When motion is detected and the lights of the group are off, the scene is recalled. If a
+///   light level sensor is set, this only happens when it is dark.
+/// - When motion is detected while the lights are dimmed, the scene is recalled again.
+/// - When no motion is detected for the dim duration, the lights are dimmed.
+/// - When no motion is detected for the off duration, the lights are turned off.
+///
+/// # Examples
+///
+/// Turn on a room when motion is detected and turn it off after 6 minutes without motion:
+/// ```no_run
+/// use huelib::automation::MotionAutomation;
+/// use chrono::Duration;
+///
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// let automation = MotionAutomation::new("Hallway".into(), "5".into(), "1".into(), "abc".into())
+///     .with_light_level_sensor_id("6".to_owned())
+///     .with_dim_after(Duration::minutes(5))
+///     .with_off_after(Duration::minutes(6));
+/// let created = automation.execute(&bridge)?;
+/// println!("Created rules {:?}", created.rule_ids);
+/// # Ok::<(), huelib::Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct MotionAutomation {
+    /// Sets the name of the automation.
+    #[setters(skip)]
+    pub name: String,
+    /// Sets the identifier of the motion sensor.
+    #[setters(skip)]
+    pub presence_sensor_id: String,
+    /// Sets the identifier of the group that is controlled.
+    #[setters(skip)]
+    pub group_id: String,
+    /// Sets the identifier of the scene that is recalled when motion is detected.
+    #[setters(skip)]
+    pub scene_id: String,
+    /// Sets the identifier of a light level sensor that must report darkness.
+    pub light_level_sensor_id: Option<String>,
+    /// Sets the duration without motion after which the lights are dimmed.
+    pub dim_after: Duration,
+    /// Sets the duration without motion after which the lights are turned off.
+    ///
+    /// Must be longer than [`dim_after`](Self::dim_after).
+    pub off_after: Duration,
+    /// Sets the amount by which the brightness is decreased when dimming.
+    pub dim_decrement: u8,
+    /// Sets the class identifier of the resourcelink.
+    pub class_id: u16,
+}
+
+impl MotionAutomation {
+    /// Creates a new [`MotionAutomation`].
+    ///
+    /// By default, the lights are dimmed after 5 minutes and turned off after 6 minutes.
+    pub fn new(
+        name: String,
+        presence_sensor_id: String,
+        group_id: String,
+        scene_id: String,
+    ) -> Self {
+        Self {
+            name,
+            presence_sensor_id,
+            group_id,
+            scene_id,
+            light_level_sensor_id: None,
+            dim_after: Duration::minutes(5),
+            off_after: Duration::minutes(6),
+            dim_decrement: 128,
+            class_id: 10020,
+        }
+    }
+
+    /// Returns the creator of the sensor that stores the state of the automation.
+    pub fn status_sensor(&self) -> sensor::Creator {
+        sensor::Creator::new(
+            format!("{} status", self.name),
+            sensor::CreatableKind::ClipGenericStatus,
+            "MotionAutomation".to_owned(),
+            "1.0".to_owned(),
+            format!("automation-{}-{}", self.presence_sensor_id, self.group_id),
+            "huelib".to_owned(),
+        )
+        .with_state(sensor::StateModifier::new().with_status(STATUS_IDLE))
+        .with_recycle(true)
+    }
+
+    /// Checks whether the lights are turned off after they are dimmed.
+    pub fn validate(&self) -> std::result::Result<(), InvalidAutomationError> {
+        if self.off_after <= self.dim_after {
+            return Err(InvalidAutomationError::OffBeforeDim {
+                dim_after: self.dim_after,
+                off_after: self.off_after,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the creators of the rules, given the identifier of the created status sensor.
+    pub fn rules(
+        &self,
+        status_sensor_id: &str,
+    ) -> std::result::Result<Vec<rule::Creator>, InvalidAutomationError> {
+        self.validate()?;
+        let presence = Address::sensor(&self.presence_sensor_id, SensorAttribute::Presence);
+        let status = Address::sensor(status_sensor_id, SensorAttribute::Status);
+        let any_on = Address::group(&self.group_id, GroupAttribute::AnyOn);
+        let recall_scene = self.group_action(json!({ "scene": self.scene_id }));
+        let set_status = |value: i32| Action {
+            address: format!("/sensors/{}/state", status_sensor_id),
+            request_method: ActionRequestMethod::Put,
+            body: json!({ "status": value }),
+        };
+
+        let mut motion = vec![
+            Condition::equals(presence.clone(), ConditionValue::Bool(true))?,
+            Condition::changed(presence.clone())?,
+            Condition::equals(any_on.clone(), ConditionValue::Bool(false))?,
+        ];
+        if let Some(id) = &self.light_level_sensor_id {
+            let dark = Address::sensor(id, SensorAttribute::Dark);
+            motion.push(Condition::equals(dark, ConditionValue::Bool(true))?);
+        }
+        let no_motion = |duration: Duration, expected_status: i32| {
+            Ok::<_, InvalidConditionError>(vec![
+                Condition::equals(presence.clone(), ConditionValue::Bool(false))?,
+                Condition::changed_delayed(presence.clone(), duration)?,
+                Condition::equals(status.clone(), ConditionValue::Int(expected_status))?,
+            ])
+        };
+
+        Ok(vec![
+            rule::Creator::new(motion, vec![recall_scene.clone(), set_status(STATUS_ON)])
+                .with_name(format!("{} motion", self.name)),
+            rule::Creator::new(
+                vec![
+                    Condition::equals(presence.clone(), ConditionValue::Bool(true))?,
+                    Condition::changed(presence.clone())?,
+                    Condition::equals(status.clone(), ConditionValue::Int(STATUS_DIMMED))?,
+                ],
+                vec![recall_scene, set_status(STATUS_ON)],
+            )
+            .with_name(format!("{} recover", self.name)),
+            rule::Creator::new(
+                no_motion(self.dim_after, STATUS_ON)?,
+                vec![
+                    self.group_action(json!({ "bri_inc": -i16::from(self.dim_decrement) })),
+                    set_status(STATUS_DIMMED),
+                ],
+            )
+            .with_name(format!("{} dim", self.name)),
+            rule::Creator::new(
+                no_motion(self.off_after, STATUS_DIMMED)?,
+                vec![
+                    self.group_action(json!({ "on": false })),
+                    set_status(STATUS_IDLE),
+                ],
+            )
+            .with_name(format!("{} off", self.name)),
+            rule::Creator::new(
+                vec![
+                    Condition::equals(any_on.clone(), ConditionValue::Bool(false))?,
+                    Condition::changed(any_on)?,
+                ],
+                vec![set_status(STATUS_IDLE)],
+            )
+            .with_name(format!("{} reset", self.name)),
+        ])
+    }
+
+    /// Returns the creator of the resourcelink that groups the created resources.
+    pub fn resourcelink(
+        &self,
+        status_sensor_id: &str,
+        rule_ids: &[String],
+    ) -> resourcelink::Creator {
+        let mut links = vec![
+            link(LinkKind::Sensor, status_sensor_id),
+            link(LinkKind::Sensor, &self.presence_sensor_id),
+            link(LinkKind::Group, &self.group_id),
+            link(LinkKind::Scene, &self.scene_id),
+        ];
+        if let Some(id) = &self.light_level_sensor_id {
+            links.push(link(LinkKind::Sensor, id));
+        }
+        links.extend(rule_ids.iter().map(|id| link(LinkKind::Rule, id)));
+        resourcelink::Creator::new(self.name.clone(), self.class_id, links)
+            .with_description("Motion automation".to_owned())
+    }
+
+    /// Creates all resources of the automation on the bridge.
+    ///
+    /// The automation is validated before any resource is created. If creating a resource fails,
+    /// the resources that were already created are deleted again.
+    pub fn execute(&self, bridge: &Bridge) -> Result<CreatedAutomation> {
+        self.validate()?;
+        let mut created = CreatedAutomation::default();
+        match self.create(bridge, &mut created) {
+            Ok(()) => Ok(created),
+            Err(e) => {
+                let _ = created.delete(bridge);
+                Err(e)
+            }
+        }
+    }
+
+    fn create(&self, bridge: &Bridge, created: &mut CreatedAutomation) -> Result<()> {
+        let status_sensor_id = bridge.create_sensor(&self.status_sensor())?;
+        created.sensor_ids.push(status_sensor_id.clone());
+        for creator in self.rules(&status_sensor_id)? {
+            created.rule_ids.push(bridge.create_rule(&creator)?);
+        }
+        let creator = self.resourcelink(&status_sensor_id, &created.rule_ids);
+        created.resourcelink_id = Some(bridge.create_resourcelink(&creator)?);
+        Ok(())
+    }
+
+    fn group_action(&self, body: JsonValue) -> Action {
+        Action {
+            address: format!("/groups/{}/action", self.group_id),
+            request_method: ActionRequestMethod::Put,
+            body,
+        }
+    }
+}
+
+fn link(kind: LinkKind, id: &str) -> Link {
+    Link {
+        kind,
+        id: id.to_owned(),
+    }
+}
+
+/// Error that occurs when an automation cannot be compiled into rules.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum InvalidAutomationError {
+    /// The lights would be turned off before they are dimmed.
+    #[error("Duration until off ({off_after}) must be longer than until dimmed ({dim_after})")]
+    OffBeforeDim {
+        /// Duration without motion after which the lights are dimmed.
+        dim_after: Duration,
+        /// Duration without motion after which the lights are turned off.
+        off_after: Duration,
+    },
+    /// A condition of a rule is invalid.
+    #[error(transparent)]
+    Condition(#[from] InvalidConditionError),
+}
+
+/// Identifiers of the resources that were created for an automation.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct CreatedAutomation {
+    /// Identifiers of the created sensors.
+    pub sensor_ids: Vec<String>,
+    /// Identifiers of the created rules.
+    pub rule_ids: Vec<String>,
+    /// Identifier of the created resourcelink.
+    pub resourcelink_id: Option<String>,
+}
+
+impl CreatedAutomation {
+    /// Deletes all resources of the automation from the bridge.
+    ///
+    /// The resources are deleted in reverse order of creation. All resources are tried to be
+    /// deleted, the first error that occurred is returned.
+    pub fn delete(&self, bridge: &Bridge) -> Result<()> {
+        let mut results = Vec::new();
+        if let Some(id) = &self.resourcelink_id {
+            results.push(bridge.delete_resourcelink(id));
+        }
+        for id in self.rule_ids.iter().rev() {
+            results.push(bridge.delete_rule(id));
+        }
+        for id in self.sensor_ids.iter().rev() {
+            results.push(bridge.delete_sensor(id));
+        }
+        results.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_motion_automation() {
+        let automation =
+            MotionAutomation::new("Hallway".into(), "5".into(), "1".into(), "abc".into())
+                .with_light_level_sensor_id("6".to_owned());
+        let rules = automation.rules("10").unwrap();
+        assert_eq!(rules.len(), 5);
+
+        let rule_json = serde_json::to_value(&rules[0]).unwrap();
+        let expected_json = json!({
+            "name": "Hallway motion",
+            "conditions": [
+                {"address": "/sensors/5/state/presence", "operator": "eq", "value": "true"},
+                {"address": "/sensors/5/state/presence", "operator": "dx"},
+                {"address": "/groups/1/state/any_on", "operator": "eq", "value": "false"},
+                {"address": "/sensors/6/state/dark", "operator": "eq", "value": "true"}
+            ],
+            "actions": [
+                {"address": "/groups/1/action", "method": "PUT", "body": {"scene": "abc"}},
+                {"address": "/sensors/10/state", "method": "PUT", "body": {"status": 1}}
+            ]
+        });
+        assert_eq!(rule_json, expected_json);
+
+        let rule_json = serde_json::to_value(&rules[3]).unwrap();
+        let expected_json = json!({
+            "name": "Hallway off",
+            "conditions": [
+                {"address": "/sensors/5/state/presence", "operator": "eq", "value": "false"},
+                {"address": "/sensors/5/state/presence", "operator": "ddx", "value": "PT00:06:00"},
+                {"address": "/sensors/10/state/status", "operator": "eq", "value": "2"}
+            ],
+            "actions": [
+                {"address": "/groups/1/action", "method": "PUT", "body": {"on": false}},
+                {"address": "/sensors/10/state", "method": "PUT", "body": {"status": 0}}
+            ]
+        });
+        assert_eq!(rule_json, expected_json);
+
+        let sensor = automation.status_sensor();
+        assert!(sensor.validate().is_ok());
+
+        let resourcelink = automation.resourcelink("10", &["20".into(), "21".into()]);
+        let resourcelink_json = serde_json::to_value(resourcelink).unwrap();
+        assert_eq!(
+            resourcelink_json["links"],
+            json!([
+                "/sensors/10",
+                "/sensors/5",
+                "/groups/1",
+                "/scenes/abc",
+                "/sensors/6",
+                "/rules/20",
+                "/rules/21"
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_motion_automation() {
+        let automation =
+            MotionAutomation::new("Hallway".into(), "5".into(), "1".into(), "abc".into())
+                .with_dim_after(Duration::minutes(5))
+                .with_off_after(Duration::minutes(5));
+        let error = InvalidAutomationError::OffBeforeDim {
+            dim_after: Duration::minutes(5),
+            off_after: Duration::minutes(5),
+        };
+        assert_eq!(automation.validate(), Err(error.clone()));
+        assert_eq!(automation.rules("10"), Err(error));
+        let automation = automation.with_off_after(Duration::minutes(6));
+        assert_eq!(automation.validate(), Ok(()));
+    }
+}
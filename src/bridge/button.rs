@@ -0,0 +1,216 @@
+use crate::resource::{sensor::ButtonAction, sensor::ButtonEvent, Sensor};
+use crate::{Bridge, Result};
+use chrono::NaiveDateTime;
+use std::collections::{HashMap, VecDeque};
+use std::{thread, time::Duration};
+
+/// A button press of a switch sensor.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ButtonPress {
+    /// Identifier of the sensor.
+    pub sensor_id: String,
+    /// Number of the button, starting at 1.
+    pub button: u8,
+    /// Action that was performed with the button.
+    pub action: ButtonAction,
+}
+
+/// Watcher that polls the switch sensors of a bridge and emits button presses.
+///
+/// Presses are de-duplicated using the `last_updated` and `button_event` attributes of the sensor
+/// state, so every event that the bridge reports is emitted exactly once. The first time a sensor is seen, only its
+/// current state is recorded and no press is emitted.
+///
+/// Since the bridge only stores the last event of a sensor, presses that happen in between two
+/// polls are lost. A short interval should be used to reduce the chance of this happening.
+///
+/// # Examples
+///
+/// Print every button press:
+/// ```no_run
+/// use huelib::bridge::ButtonWatcher;
+///
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// let mut watcher = ButtonWatcher::new(bridge);
+/// for press in watcher.presses() {
+///     let press = press?;
+///     println!("Sensor {}: button {} {:?}", press.sensor_id, press.button, press.action);
+/// }
+/// # Ok::<(), huelib::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct ButtonWatcher {
+    bridge: Bridge,
+    interval: Duration,
+    last_events: HashMap<String, (Option<NaiveDateTime>, ButtonEvent)>,
+}
+
+impl ButtonWatcher {
+    /// Creates a new [`ButtonWatcher`] that polls the sensors every 200 milliseconds.
+    pub fn new(bridge: Bridge) -> Self {
+        Self {
+            bridge,
+            interval: Duration::from_millis(200),
+            last_events: HashMap::new(),
+        }
+    }
+
+    /// Sets the interval between two polls.
+    pub fn with_interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// Returns the interval between two polls.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Fetches the sensors and returns the button presses since the last poll.
+    ///
+    /// This does not wait for the interval to elapse.
+    pub fn poll(&mut self) -> Result<Vec<ButtonPress>> {
+        let sensors = self.bridge.get_all_sensors()?;
+        Ok(self.process(&sensors))
+    }
+
+    /// Returns the button presses of the given sensors that were not yet reported.
+    ///
+    /// This is useful if the sensors are already fetched by other means, for example by a
+    /// [`Watcher`](super::Watcher).
+    pub fn process(&mut self, sensors: &[Sensor]) -> Vec<ButtonPress> {
+        let mut presses = Vec::new();
+        for sensor in sensors {
+            let event = match sensor.button_event() {
+                Some(v) => v,
+                None => continue,
+            };
+            // The last updated time only has a resolution of one second, so the event itself is
+            // compared as well.
+            let last_updated = sensor.kind.last_updated();
+            let current = (last_updated, event);
+            let previous = self.last_events.insert(sensor.id.clone(), current);
+            if matches!(previous, Some(v) if v != current) && last_updated.is_some() {
+                presses.push(ButtonPress {
+                    sensor_id: sensor.id.clone(),
+                    button: event.button,
+                    action: event.action,
+                });
+            }
+        }
+        presses
+    }
+
+    /// Returns a blocking iterator over the button presses.
+    ///
+    /// The iterator polls the bridge and waits for the interval between two polls. It never ends,
+    /// errors that occur while polling are returned as items.
+    pub fn presses(&mut self) -> ButtonPresses<'_> {
+        ButtonPresses {
+            watcher: self,
+            pending: VecDeque::new(),
+            polled: false,
+        }
+    }
+}
+
+/// Blocking iterator over the button presses of a [`ButtonWatcher`].
+///
+/// This struct is created by the [`ButtonWatcher::presses`] method.
+#[derive(Debug)]
+pub struct ButtonPresses<'a> {
+    watcher: &'a mut ButtonWatcher,
+    pending: VecDeque<ButtonPress>,
+    polled: bool,
+}
+
+impl Iterator for ButtonPresses<'_> {
+    type Item = Result<ButtonPress>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(press) = self.pending.pop_front() {
+                return Some(Ok(press));
+            }
+            if self.polled {
+                thread::sleep(self.watcher.interval);
+            }
+            self.polled = true;
+            match self.watcher.poll() {
+                Ok(presses) => self.pending.extend(presses),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::switch;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn process_deduplicates() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user");
+        let mut watcher = ButtonWatcher::new(bridge);
+
+        let sensors = vec![switch("1", 1002, "2020-01-01T00:00:00")];
+        assert_eq!(watcher.process(&sensors), vec![]);
+        assert_eq!(watcher.process(&sensors), vec![]);
+
+        let sensors = vec![
+            switch("1", 1002, "2020-01-01T00:00:05"),
+            switch("2", 2000, "2020-01-01T00:00:05"),
+        ];
+        assert_eq!(
+            watcher.process(&sensors),
+            vec![ButtonPress {
+                sensor_id: "1".into(),
+                button: 1,
+                action: ButtonAction::ShortRelease,
+            }]
+        );
+        assert_eq!(watcher.process(&sensors), vec![]);
+
+        let sensors = vec![switch("2", 2001, "2020-01-01T00:00:06")];
+        assert_eq!(
+            watcher.process(&sensors),
+            vec![ButtonPress {
+                sensor_id: "2".into(),
+                button: 2,
+                action: ButtonAction::Repeat,
+            }]
+        );
+    }
+
+    #[test]
+    fn process_same_second() {
+        let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), "user");
+        let mut watcher = ButtonWatcher::new(bridge);
+
+        let sensors = vec![switch("1", 1002, "2020-01-01T00:00:00")];
+        assert_eq!(watcher.process(&sensors), vec![]);
+        let sensors = vec![switch("1", 1000, "2020-01-01T00:00:05")];
+        assert_eq!(
+            watcher.process(&sensors),
+            vec![ButtonPress {
+                sensor_id: "1".into(),
+                button: 1,
+                action: ButtonAction::InitialPress,
+            }]
+        );
+        let sensors = vec![switch("1", 1002, "2020-01-01T00:00:05")];
+        assert_eq!(
+            watcher.process(&sensors),
+            vec![ButtonPress {
+                sensor_id: "1".into(),
+                button: 1,
+                action: ButtonAction::ShortRelease,
+            }]
+        );
+        assert_eq!(watcher.process(&sensors), vec![]);
+    }
+}
@@ -4,17 +4,21 @@ use serde::de::DeserializeOwned;
 use serde_json::Value as JsonValue;
 use std::{collections::HashMap, net::IpAddr};
 
+mod button;
 #[cfg(feature = "upnp-description")]
 mod description;
 mod discover;
 mod register;
+mod watcher;
 
+pub use button::{ButtonPress, ButtonPresses, ButtonWatcher};
 #[cfg(feature = "upnp-description")]
 pub use description::{
     description, Description, DescriptionDevice, DescriptionIcon, DescriptionSpecVersion,
 };
 pub use discover::discover_nupnp;
 pub use register::{register_user, register_user_with_clientkey};
+pub use watcher::{Event, Events, LightStateChange, Snapshot, Watcher};
 
 type ResponsesModified = Vec<Response<Modified>>;
 
@@ -87,7 +91,10 @@ impl Bridge {
         S: AsRef<str>,
         T: DeserializeOwned,
     {
-        let url = format!("{}/{}", self.api_url, url_suffix.as_ref());
+        let url = match url_suffix.as_ref() {
+            "" => self.api_url.clone(),
+            v => format!("{}/{}", self.api_url, v),
+        };
         let request = match request_method {
             RequestMethod::Put => ureq::put(&url),
             RequestMethod::Post => ureq::post(&url),
@@ -101,6 +108,16 @@ impl Bridge {
         Ok(response.into_json()?)
     }
 
+    /// Returns a [`Watcher`] that polls this bridge for changes.
+    pub fn watcher(&self) -> Watcher {
+        Watcher::new(self.clone())
+    }
+
+    /// Returns a [`ButtonWatcher`] that polls the switch sensors of this bridge.
+    pub fn button_watcher(&self) -> ButtonWatcher {
+        ButtonWatcher::new(self.clone())
+    }
+
     /// Modifies the configuration of the bridge.
     pub fn set_config(&self, modifier: &resource::config::Modifier) -> Result<ResponsesModified> {
         modifier.execute(self, ())
@@ -185,11 +202,8 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("lights/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("lights/{}", id.into()), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
@@ -254,11 +268,8 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("groups/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("groups/{}", id.into()), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
@@ -311,11 +322,8 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("scenes/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("scenes/{}", id.into()), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
@@ -374,7 +382,7 @@ impl Bridge {
         S: Into<String>,
     {
         let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("schedules/{}", id.into()),
+            format!("schedules/{}", id.into()),
             RequestMethod::Delete,
             None,
         )?;
@@ -384,6 +392,63 @@ impl Bridge {
         Ok(())
     }
 
+    /// Creates a new schedule command from a [`Creator`] that is sent as the user of this bridge.
+    ///
+    /// [`Creator`]: resource::Creator
+    pub fn command_from_creator<C>(&self, creator: &C) -> Result<resource::schedule::Command>
+    where
+        C: Creator,
+    {
+        Ok(resource::schedule::Command::from_creator(
+            creator,
+            &self.username,
+        )?)
+    }
+
+    /// Creates a new schedule command from a [`Modifier`] that is sent as the user of this bridge.
+    ///
+    /// [`Modifier`]: resource::Modifier
+    pub fn command_from_modifier<M>(
+        &self,
+        modifier: &M,
+        id: M::Id,
+    ) -> Result<resource::schedule::Command>
+    where
+        M: Modifier,
+    {
+        Ok(resource::schedule::Command::from_modifier(
+            modifier,
+            id,
+            &self.username,
+        )?)
+    }
+
+    /// Creates a new schedule command from a [`Scanner`] that is sent as the user of this bridge.
+    ///
+    /// [`Scanner`]: resource::Scanner
+    pub fn command_from_scanner<S>(&self, scanner: &S) -> Result<resource::schedule::Command>
+    where
+        S: Scanner,
+    {
+        Ok(resource::schedule::Command::from_scanner(
+            scanner,
+            &self.username,
+        )?)
+    }
+
+    /// Creates a new schedule command that deletes a resource and is sent as the user of this
+    /// bridge.
+    pub fn command_delete<S>(
+        &self,
+        kind: resource::resourcelink::LinkKind,
+        id: S,
+    ) -> resource::schedule::Command
+    where
+        S: Into<String>,
+    {
+        resource::schedule::Command::delete(kind, id.into(), &self.username)
+    }
+
     /// Creates a new resourcelink and returns the identifier.
     pub fn create_resourcelink(&self, creator: &resource::resourcelink::Creator) -> Result<String> {
         creator.execute(self)
@@ -431,7 +496,7 @@ impl Bridge {
         S: Into<String>,
     {
         let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("resourcelinks/{}", id.into()),
+            format!("resourcelinks/{}", id.into()),
             RequestMethod::Delete,
             None,
         )?;
@@ -441,6 +506,15 @@ impl Bridge {
         Ok(())
     }
 
+    /// Creates a new sensor and returns the identifier.
+    ///
+    /// Returns an error if the initial state or configuration is not supported by the sensor
+    /// type, see [`Creator::validate`](resource::sensor::Creator::validate).
+    pub fn create_sensor(&self, creator: &resource::sensor::Creator) -> Result<String> {
+        creator.validate()?;
+        creator.execute(self)
+    }
+
     /// Modifies attributes of a sensor.
     pub fn set_sensor_attribute<S>(
         &self,
@@ -465,6 +539,19 @@ impl Bridge {
         modifier.execute(self, id.into())
     }
 
+    /// Modifies the state of a sensor after checking the modifier against its type.
+    ///
+    /// Returns an error without sending a request if an attribute is not supported by the sensor
+    /// type, see [`StateModifier::validate`](resource::sensor::StateModifier::validate).
+    pub fn set_sensor_state_checked(
+        &self,
+        sensor: &resource::Sensor,
+        modifier: &resource::sensor::StateModifier,
+    ) -> Result<ResponsesModified> {
+        modifier.validate(sensor.kind.type_name())?;
+        modifier.execute(self, sensor.id.clone())
+    }
+
     /// Modifies the configuration of a sensor.
     pub fn set_sensor_config<S>(
         &self,
@@ -477,6 +564,19 @@ impl Bridge {
         modifier.execute(self, id.into())
     }
 
+    /// Modifies the configuration of a sensor after checking the modifier against its type.
+    ///
+    /// Returns an error without sending a request if an attribute is not supported by the sensor
+    /// type, see [`ConfigModifier::validate`](resource::sensor::ConfigModifier::validate).
+    pub fn set_sensor_config_checked(
+        &self,
+        sensor: &resource::Sensor,
+        modifier: &resource::sensor::ConfigModifier,
+    ) -> Result<ResponsesModified> {
+        modifier.validate(sensor.kind.type_name())?;
+        modifier.execute(self, sensor.id.clone())
+    }
+
     /// Returns a sensor.
     pub fn get_sensor<S>(&self, id: S) -> Result<resource::Sensor>
     where
@@ -528,7 +628,7 @@ impl Bridge {
         S: Into<String>,
     {
         let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("sensors/{}", id.into()),
+            format!("sensors/{}", id.into()),
             RequestMethod::Delete,
             None,
         )?;
@@ -579,7 +679,7 @@ impl Bridge {
         S: Into<String>,
     {
         let response: Vec<Response<JsonValue>> =
-            self.api_request(&format!("rules/{}", id.into()), RequestMethod::Delete, None)?;
+            self.api_request(format!("rules/{}", id.into()), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
@@ -0,0 +1,611 @@
+use crate::resource::{
+    self, light, resourcelink::LinkKind, Alert, ColorMode, Effect, RequestMethod,
+};
+use crate::{util, Bridge, Result};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Deserializer};
+use std::collections::{HashMap, VecDeque};
+use std::{thread, time::Duration};
+
+/// Snapshot of the resources of a bridge at a given point in time.
+///
+/// The keys of the maps are the identifiers of the resources. Collections that were not fetched
+/// are left empty.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    /// Lights of the bridge.
+    pub lights: HashMap<String, resource::Light>,
+    /// Groups of the bridge.
+    pub groups: HashMap<String, resource::Group>,
+    /// Scenes of the bridge.
+    pub scenes: HashMap<String, resource::Scene>,
+    /// Schedules of the bridge.
+    pub schedules: HashMap<String, resource::Schedule>,
+    /// Rules of the bridge.
+    pub rules: HashMap<String, resource::Rule>,
+    /// Sensors of the bridge.
+    pub sensors: HashMap<String, resource::Sensor>,
+    /// Resourcelinks of the bridge.
+    pub resourcelinks: HashMap<String, resource::Resourcelink>,
+}
+
+fn deserialize_map<'de, D, T>(deserializer: D) -> std::result::Result<HashMap<String, T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + WithId,
+{
+    let map: HashMap<String, T> = Deserialize::deserialize(deserializer)?;
+    Ok(map
+        .into_iter()
+        .map(|(id, resource)| (id.clone(), resource.with_id(id)))
+        .collect())
+}
+
+trait WithId {
+    fn with_id(self, id: String) -> Self;
+}
+
+macro_rules! impl_with_id {
+    ($($t:ty),*) => {
+        $(
+            impl WithId for $t {
+                fn with_id(self, id: String) -> Self {
+                    <$t>::with_id(self, id)
+                }
+            }
+        )*
+    };
+}
+
+impl_with_id!(
+    resource::Light,
+    resource::Group,
+    resource::Scene,
+    resource::Schedule,
+    resource::Rule,
+    resource::Sensor,
+    resource::Resourcelink
+);
+
+impl<'de> Deserialize<'de> for Snapshot {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct FullState {
+            #[serde(default, deserialize_with = "deserialize_map")]
+            lights: HashMap<String, resource::Light>,
+            #[serde(default, deserialize_with = "deserialize_map")]
+            groups: HashMap<String, resource::Group>,
+            #[serde(default, deserialize_with = "deserialize_map")]
+            scenes: HashMap<String, resource::Scene>,
+            #[serde(default, deserialize_with = "deserialize_map")]
+            schedules: HashMap<String, resource::Schedule>,
+            #[serde(default, deserialize_with = "deserialize_map")]
+            rules: HashMap<String, resource::Rule>,
+            #[serde(default, deserialize_with = "deserialize_map")]
+            sensors: HashMap<String, resource::Sensor>,
+            #[serde(default, deserialize_with = "deserialize_map")]
+            resourcelinks: HashMap<String, resource::Resourcelink>,
+        }
+        let v = FullState::deserialize(deserializer)?;
+        Ok(Self {
+            lights: v.lights,
+            groups: v.groups,
+            scenes: v.scenes,
+            schedules: v.schedules,
+            rules: v.rules,
+            sensors: v.sensors,
+            resourcelinks: v.resourcelinks,
+        })
+    }
+}
+
+fn into_map<T, F>(resources: Vec<T>, id: F) -> HashMap<String, T>
+where
+    F: Fn(&T) -> &str,
+{
+    resources
+        .into_iter()
+        .map(|v| (id(&v).to_owned(), v))
+        .collect()
+}
+
+impl Snapshot {
+    /// Fetches a snapshot of the given collections from the bridge.
+    ///
+    /// If `collections` is `None`, the full state of the bridge is fetched with a single request.
+    pub fn fetch(bridge: &Bridge, collections: Option<&[LinkKind]>) -> Result<Self> {
+        let collections = match collections {
+            Some(v) => v,
+            None => {
+                return super::parse_response(bridge.api_request("", RequestMethod::Get, None)?)
+            }
+        };
+        let mut snapshot = Self::default();
+        for kind in collections {
+            match kind {
+                LinkKind::Light => {
+                    snapshot.lights = into_map(bridge.get_all_lights()?, |v| &v.id);
+                }
+                LinkKind::Group => {
+                    snapshot.groups = into_map(bridge.get_all_groups()?, |v| &v.id);
+                }
+                LinkKind::Scene => {
+                    snapshot.scenes = into_map(bridge.get_all_scenes()?, |v| &v.id);
+                }
+                LinkKind::Schedule => {
+                    snapshot.schedules = into_map(bridge.get_all_schedules()?, |v| &v.id);
+                }
+                LinkKind::Rule => {
+                    snapshot.rules = into_map(bridge.get_all_rules()?, |v| &v.id);
+                }
+                LinkKind::Sensor => {
+                    snapshot.sensors = into_map(bridge.get_all_sensors()?, |v| &v.id);
+                }
+                LinkKind::Resourcelink => {
+                    snapshot.resourcelinks = into_map(bridge.get_all_resourcelinks()?, |v| &v.id);
+                }
+            }
+        }
+        Ok(snapshot)
+    }
+
+    /// Compares this snapshot with a newer one and returns the events that happened in between.
+    ///
+    /// The events are ordered by the kind of the resource and then by the identifier.
+    pub fn diff(&self, newer: &Snapshot) -> Vec<Event> {
+        let mut events = Vec::new();
+        diff_collection(
+            &mut events,
+            LinkKind::Light,
+            &self.lights,
+            &newer.lights,
+            diff_light,
+        );
+        diff_collection(
+            &mut events,
+            LinkKind::Group,
+            &self.groups,
+            &newer.groups,
+            diff_resource,
+        );
+        diff_collection(
+            &mut events,
+            LinkKind::Scene,
+            &self.scenes,
+            &newer.scenes,
+            diff_resource,
+        );
+        diff_collection(
+            &mut events,
+            LinkKind::Schedule,
+            &self.schedules,
+            &newer.schedules,
+            diff_resource,
+        );
+        diff_collection(
+            &mut events,
+            LinkKind::Rule,
+            &self.rules,
+            &newer.rules,
+            diff_resource,
+        );
+        diff_collection(
+            &mut events,
+            LinkKind::Sensor,
+            &self.sensors,
+            &newer.sensors,
+            diff_sensor,
+        );
+        diff_collection(
+            &mut events,
+            LinkKind::Resourcelink,
+            &self.resourcelinks,
+            &newer.resourcelinks,
+            diff_resource,
+        );
+        events
+    }
+}
+
+fn diff_collection<T, F>(
+    events: &mut Vec<Event>,
+    kind: LinkKind,
+    old: &HashMap<String, T>,
+    new: &HashMap<String, T>,
+    diff: F,
+) where
+    F: Fn(&mut Vec<Event>, LinkKind, &str, &T, &T),
+{
+    let mut ids: Vec<&String> = old.keys().chain(new.keys()).collect();
+    ids.sort_by(|a, b| util::compare_ids(a, b));
+    ids.dedup();
+    for id in ids {
+        match (old.get(id), new.get(id)) {
+            (None, Some(_)) => events.push(Event::Added {
+                kind,
+                id: id.clone(),
+            }),
+            (Some(_), None) => events.push(Event::Removed {
+                kind,
+                id: id.clone(),
+            }),
+            (Some(old), Some(new)) => diff(events, kind, id, old, new),
+            (None, None) => {}
+        }
+    }
+}
+
+fn diff_resource<T: PartialEq>(
+    events: &mut Vec<Event>,
+    kind: LinkKind,
+    id: &str,
+    old: &T,
+    new: &T,
+) {
+    if old != new {
+        events.push(Event::Modified {
+            kind,
+            id: id.to_owned(),
+        });
+    }
+}
+
+fn diff_light(
+    events: &mut Vec<Event>,
+    kind: LinkKind,
+    id: &str,
+    old: &resource::Light,
+    new: &resource::Light,
+) {
+    let changes = LightStateChange::diff(&old.state, &new.state);
+    if !changes.is_empty() {
+        events.push(Event::LightStateChanged {
+            id: id.to_owned(),
+            changes,
+        });
+    }
+    if old.state.reachable && !new.state.reachable {
+        events.push(Event::LightUnreachable { id: id.to_owned() });
+    }
+    let without_state = resource::Light {
+        state: old.state.clone(),
+        ..new.clone()
+    };
+    diff_resource(events, kind, id, old, &without_state);
+}
+
+fn diff_sensor(
+    events: &mut Vec<Event>,
+    kind: LinkKind,
+    id: &str,
+    old: &resource::Sensor,
+    new: &resource::Sensor,
+) {
+    match (old.kind.last_updated(), new.kind.last_updated()) {
+        (Some(old_time), Some(new_time)) if new_time > old_time => {
+            events.push(Event::SensorUpdated {
+                id: id.to_owned(),
+                last_updated: new_time,
+            })
+        }
+        (None, Some(new_time)) => events.push(Event::SensorUpdated {
+            id: id.to_owned(),
+            last_updated: new_time,
+        }),
+        _ => {}
+    }
+    let old = resource::Sensor {
+        kind: old.kind.without_state(),
+        ..old.clone()
+    };
+    let new = resource::Sensor {
+        kind: new.kind.without_state(),
+        ..new.clone()
+    };
+    diff_resource(events, kind, id, &old, &new);
+}
+
+/// An event that is emitted when the resources of a bridge change.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// A resource was added.
+    Added {
+        /// Kind of the resource.
+        kind: LinkKind,
+        /// Identifier of the resource.
+        id: String,
+    },
+    /// A resource was removed.
+    Removed {
+        /// Kind of the resource.
+        kind: LinkKind,
+        /// Identifier of the resource.
+        id: String,
+    },
+    /// Attributes of a resource changed.
+    ///
+    /// This is not emitted for changes of the light state or the sensor state, see
+    /// [`LightStateChanged`] and [`SensorUpdated`].
+    ///
+    /// [`LightStateChanged`]: Self::LightStateChanged
+    /// [`SensorUpdated`]: Self::SensorUpdated
+    Modified {
+        /// Kind of the resource.
+        kind: LinkKind,
+        /// Identifier of the resource.
+        id: String,
+    },
+    /// The state of a light changed.
+    LightStateChanged {
+        /// Identifier of the light.
+        id: String,
+        /// Attributes of the state that changed.
+        changes: Vec<LightStateChange>,
+    },
+    /// A light that was reachable became unreachable.
+    LightUnreachable {
+        /// Identifier of the light.
+        id: String,
+    },
+    /// The `last_updated` attribute of a sensor state advanced.
+    SensorUpdated {
+        /// Identifier of the sensor.
+        id: String,
+        /// New value of the `last_updated` attribute.
+        last_updated: NaiveDateTime,
+    },
+}
+
+/// Attribute of a light state that changed, containing the new value.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum LightStateChange {
+    On(Option<bool>),
+    Brightness(Option<u8>),
+    Hue(Option<u16>),
+    Saturation(Option<u8>),
+    ColorSpaceCoordinates(Option<(f32, f32)>),
+    ColorTemperature(Option<u16>),
+    Alert(Option<Alert>),
+    Effect(Option<Effect>),
+    ColorMode(Option<ColorMode>),
+    Reachable(bool),
+}
+
+impl LightStateChange {
+    fn diff(old: &light::State, new: &light::State) -> Vec<Self> {
+        let mut changes = Vec::new();
+        macro_rules! push_changes {
+            ($($field:ident => $variant:ident,)*) => {
+                $(
+                    if old.$field != new.$field {
+                        changes.push(Self::$variant(new.$field));
+                    }
+                )*
+            };
+        }
+        push_changes! {
+            on => On,
+            brightness => Brightness,
+            hue => Hue,
+            saturation => Saturation,
+            color_space_coordinates => ColorSpaceCoordinates,
+            color_temperature => ColorTemperature,
+            alert => Alert,
+            effect => Effect,
+            color_mode => ColorMode,
+            reachable => Reachable,
+        }
+        changes
+    }
+}
+
+/// Watcher that polls a bridge and emits events when resources change.
+///
+/// The watcher keeps the last fetched [`Snapshot`] and compares it to the next one. The first poll
+/// only records the initial snapshot and does not emit any events.
+///
+/// # Examples
+///
+/// Print every light state change:
+/// ```no_run
+/// use huelib::bridge::{Event, Watcher};
+/// use huelib::resource::resourcelink::LinkKind;
+/// use std::time::Duration;
+///
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// let mut watcher = Watcher::new(bridge)
+///     .with_interval(Duration::from_millis(500))
+///     .with_collections(vec![LinkKind::Light]);
+/// for event in watcher.events() {
+///     if let Ok(Event::LightStateChanged { id, changes }) = event {
+///         println!("Light {} changed: {:?}", id, changes);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Watcher {
+    bridge: Bridge,
+    interval: Duration,
+    collections: Option<Vec<LinkKind>>,
+    snapshot: Option<Snapshot>,
+}
+
+impl Watcher {
+    /// Creates a new [`Watcher`] that polls the full state of the bridge every second.
+    pub fn new(bridge: Bridge) -> Self {
+        Self {
+            bridge,
+            interval: Duration::from_secs(1),
+            collections: None,
+            snapshot: None,
+        }
+    }
+
+    /// Sets the interval between two polls.
+    pub fn with_interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// Only polls the given collections instead of the full state of the bridge.
+    pub fn with_collections(self, collections: Vec<LinkKind>) -> Self {
+        Self {
+            collections: Some(collections),
+            ..self
+        }
+    }
+
+    /// Returns the interval between two polls.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns the last fetched snapshot.
+    pub fn snapshot(&self) -> Option<&Snapshot> {
+        self.snapshot.as_ref()
+    }
+
+    /// Fetches a new snapshot and returns the events since the last poll.
+    ///
+    /// This does not wait for the interval to elapse.
+    pub fn poll(&mut self) -> Result<Vec<Event>> {
+        let snapshot = Snapshot::fetch(&self.bridge, self.collections.as_deref())?;
+        let events = match &self.snapshot {
+            Some(v) => v.diff(&snapshot),
+            None => Vec::new(),
+        };
+        self.snapshot = Some(snapshot);
+        Ok(events)
+    }
+
+    /// Returns a blocking iterator over the events.
+    ///
+    /// The iterator polls the bridge and waits for the interval between two polls. It never ends,
+    /// errors that occur while polling are returned as items.
+    pub fn events(&mut self) -> Events<'_> {
+        Events {
+            watcher: self,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+/// Blocking iterator over the events of a [`Watcher`].
+///
+/// This struct is created by the [`Watcher::events`] method.
+#[derive(Debug)]
+pub struct Events<'a> {
+    watcher: &'a mut Watcher,
+    pending: VecDeque<Event>,
+}
+
+impl Iterator for Events<'_> {
+    type Item = Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.watcher.snapshot.is_some() {
+                thread::sleep(self.watcher.interval);
+            }
+            match self.watcher.poll() {
+                Ok(events) => self.pending.extend(events),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn light(on: bool, reachable: bool) -> resource::Light {
+        let mut light = fixtures::light("", "C");
+        light.state.on = Some(on);
+        light.state.reachable = reachable;
+        light
+    }
+
+    fn sensor(name: &str, last_updated: &str) -> resource::Sensor {
+        resource::Sensor {
+            name: name.to_owned(),
+            ..fixtures::switch("", 1002, last_updated)
+        }
+    }
+
+    #[test]
+    fn diff_snapshots() {
+        let mut old = Snapshot::default();
+        old.lights.insert("1".into(), light(false, true));
+        old.lights.insert("2".into(), light(true, true));
+        old.sensors
+            .insert("3".into(), sensor("switch", "2020-01-01T00:00:00"));
+        old.sensors
+            .insert("4".into(), sensor("switch", "2020-01-01T00:00:00"));
+
+        let mut new = Snapshot::default();
+        new.lights.insert("1".into(), light(true, true));
+        new.lights.insert("10".into(), light(true, false));
+        new.sensors
+            .insert("3".into(), sensor("switch", "2020-01-01T00:00:05"));
+        new.sensors
+            .insert("4".into(), sensor("renamed", "2020-01-01T00:00:00"));
+
+        let events = old.diff(&new);
+        assert_eq!(
+            events,
+            vec![
+                Event::LightStateChanged {
+                    id: "1".into(),
+                    changes: vec![LightStateChange::On(Some(true))],
+                },
+                Event::Removed {
+                    kind: LinkKind::Light,
+                    id: "2".into(),
+                },
+                Event::Added {
+                    kind: LinkKind::Light,
+                    id: "10".into(),
+                },
+                Event::SensorUpdated {
+                    id: "3".into(),
+                    last_updated: NaiveDateTime::parse_from_str(
+                        "2020-01-01T00:00:05",
+                        "%Y-%m-%dT%H:%M:%S"
+                    )
+                    .unwrap(),
+                },
+                Event::Modified {
+                    kind: LinkKind::Sensor,
+                    id: "4".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_unreachable_light() {
+        let mut old = Snapshot::default();
+        old.lights.insert("1".into(), light(true, true));
+        let mut new = Snapshot::default();
+        new.lights.insert("1".into(), light(true, false));
+        let events = old.diff(&new);
+        assert_eq!(
+            events,
+            vec![
+                Event::LightStateChanged {
+                    id: "1".into(),
+                    changes: vec![LightStateChange::Reachable(false)],
+                },
+                Event::LightUnreachable { id: "1".into() },
+            ]
+        );
+    }
+}
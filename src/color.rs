@@ -1,4 +1,5 @@
-use std::num::ParseIntError;
+use crate::resource::{light, Adjust, ColorMode};
+use std::{num::ParseIntError, str::FromStr};
 use thiserror::Error as ThisError;
 
 /// Struct for setting the color of a light.
@@ -61,8 +62,8 @@ impl Color {
         let z = red * 0.000_000 + green * 0.053_077 + blue * 1.035_763;
         Self {
             space_coordinates: (
-                x / (x + y + z + std::f32::MIN_POSITIVE),
-                y / (x + y + z + std::f32::MIN_POSITIVE),
+                x / (x + y + z + f32::MIN_POSITIVE),
+                y / (x + y + z + f32::MIN_POSITIVE),
             ),
             brightness: Some((y * 255.0) as u8),
         }
@@ -70,7 +71,8 @@ impl Color {
 
     /// Creates a new color from a hex value.
     ///
-    /// The string must begin with a `#` followed by either 3 or 6 hexadecimal digits.
+    /// The string must begin with a `#` followed by either 3, 4, 6 or 8 hexadecimal digits. The
+    /// alpha channel of the 4 and 8 digit versions is ignored.
     ///
     /// This changes the color and brightness of a light.
     ///
@@ -96,25 +98,497 @@ impl Color {
     /// ```
     pub fn from_hex(s: impl AsRef<str>) -> Result<Self, ParseHexError> {
         let s = s.as_ref();
+        if !s.is_ascii() || !s.starts_with('#') {
+            return Err(ParseHexError::InvalidFormat);
+        }
         match s.len() {
-            4 => {
+            4 | 5 => {
                 let red = u8::from_str_radix(&s[1..2], 16)?;
                 let green = u8::from_str_radix(&s[2..3], 16)?;
                 let blue = u8::from_str_radix(&s[3..4], 16)?;
+                if s.len() == 5 {
+                    u8::from_str_radix(&s[4..5], 16)?;
+                }
                 Ok(Self::from_rgb(
                     red * 16 + red,
                     green * 16 + green,
                     blue * 16 + blue,
                 ))
             }
-            7 => Ok(Self::from_rgb(
-                u8::from_str_radix(&s[1..3], 16)?,
-                u8::from_str_radix(&s[3..5], 16)?,
-                u8::from_str_radix(&s[5..7], 16)?,
-            )),
+            7 | 9 => {
+                let red = u8::from_str_radix(&s[1..3], 16)?;
+                let green = u8::from_str_radix(&s[3..5], 16)?;
+                let blue = u8::from_str_radix(&s[5..7], 16)?;
+                if s.len() == 9 {
+                    u8::from_str_radix(&s[7..9], 16)?;
+                }
+                Ok(Self::from_rgb(red, green, blue))
+            }
             _ => Err(ParseHexError::InvalidLenght),
         }
     }
+
+    /// Creates a new color from a CSS color string.
+    ///
+    /// Supported are hex colors, the named colors and the `rgb()`, `rgba()`, `hsl()` and `hsla()`
+    /// functions. The alpha channel is ignored.
+    ///
+    /// This changes the color and brightness of a light.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::Color;
+    ///
+    /// # fn main() -> Result<(), huelib::color::ParseColorError> {
+    /// let color = Color::from_css("rebeccapurple")?;
+    /// assert_eq!(color, Color::from_css("rgb(102, 51, 153)")?);
+    /// let color: Color = "hsl(120deg 100% 50%)".parse()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_css(s: impl AsRef<str>) -> Result<Self, ParseColorError> {
+        let s = s.as_ref().trim();
+        if s.starts_with('#') && s.is_ascii() {
+            return Ok(Self::from_hex(s)?);
+        }
+        let value = s.to_ascii_lowercase();
+        if let Some(index) = value.find('(') {
+            let invalid = || ParseColorError::InvalidFunction(s.to_owned());
+            let name = value[..index].trim();
+            let arguments = value[index + 1..].strip_suffix(')').ok_or_else(invalid)?;
+            let arguments: Vec<&str> = arguments
+                .split('/')
+                .next()
+                .unwrap_or_default()
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|v| !v.is_empty())
+                .collect();
+            let arguments = match arguments.len() {
+                3 | 4 => &arguments[..3],
+                _ => return Err(invalid()),
+            };
+            let percentage = |v: &str| v.strip_suffix('%')?.parse::<f32>().ok();
+            return match name {
+                "rgb" | "rgba" => {
+                    let mut channels = [0; 3];
+                    for (channel, argument) in channels.iter_mut().zip(arguments) {
+                        let value = match percentage(argument) {
+                            Some(v) => v * 2.55,
+                            None => argument.parse::<f32>().map_err(|_| invalid())?,
+                        };
+                        *channel = value.round().clamp(0.0, 255.0) as u8;
+                    }
+                    Ok(Self::from_rgb(channels[0], channels[1], channels[2]))
+                }
+                "hsl" | "hsla" => {
+                    let hue = arguments[0].strip_suffix("deg").unwrap_or(arguments[0]);
+                    let hue = hue.parse::<f32>().map_err(|_| invalid())?;
+                    let saturation = percentage(arguments[1]).ok_or_else(invalid)?;
+                    let lightness = percentage(arguments[2]).ok_or_else(invalid)?;
+                    Ok(Self::from_hsl(hue, saturation / 100.0, lightness / 100.0))
+                }
+                _ => Err(invalid()),
+            };
+        }
+        match NAMED_COLORS.binary_search_by(|(name, _)| name.cmp(&value.as_str())) {
+            Ok(index) => {
+                let rgb = NAMED_COLORS[index].1;
+                Ok(Self::from_rgb(
+                    (rgb >> 16) as u8,
+                    (rgb >> 8) as u8,
+                    rgb as u8,
+                ))
+            }
+            Err(_) => Err(ParseColorError::UnknownName(s.to_owned())),
+        }
+    }
+
+    /// Creates a new color from hue, saturation and value.
+    ///
+    /// The hue is given in degrees, saturation and value must be between 0 and 1.
+    ///
+    /// This changes the color and brightness of a light.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let (red, green, blue) = hsv_to_rgb(hue, saturation, value);
+        Self::from_rgb(red, green, blue)
+    }
+
+    /// Creates a new color from hue, saturation and lightness.
+    ///
+    /// The hue is given in degrees, saturation and lightness must be between 0 and 1.
+    ///
+    /// This changes the color and brightness of a light.
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let value = lightness + saturation * lightness.min(1.0 - lightness);
+        let saturation = match value > 0.0 {
+            true => 2.0 * (1.0 - lightness / value),
+            false => 0.0,
+        };
+        Self::from_hsv(hue, saturation, value)
+    }
+
+    /// Creates a new color from the current state of a light.
+    ///
+    /// The color is taken from the attribute that matches the color mode of the light and the
+    /// brightness from the brightness of the light. Returns `None` if the light has no color mode
+    /// or the matching attribute is not set.
+    ///
+    /// # Examples
+    ///
+    /// Get the current color of a light as a hex string:
+    /// ```no_run
+    /// use huelib::Color;
+    ///
+    /// # let bridge = huelib::Bridge::new(std::net::IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// let light = bridge.get_light("1")?;
+    /// if let Some(color) = Color::from_light_state(&light.state) {
+    ///     println!("{}", color.to_hex());
+    /// }
+    /// # Ok::<(), huelib::Error>(())
+    /// ```
+    pub fn from_light_state(state: &light::State) -> Option<Self> {
+        let space_coordinates = match state.color_mode? {
+            ColorMode::ColorSpaceCoordinates => state.color_space_coordinates?,
+            ColorMode::HueAndSaturation => {
+                let hue = state.hue? as f32 / 65535.0 * 360.0;
+                let saturation = state.saturation? as f32 / 254.0;
+                Self::from_hsv(hue, saturation, 1.0).space_coordinates
+            }
+            ColorMode::ColorTemperature => {
+                ColorTemperature::from_mired(state.color_temperature?)
+                    .to_color()
+                    .space_coordinates
+            }
+        };
+        Some(Self {
+            space_coordinates,
+            brightness: state.brightness,
+        })
+    }
+
+    /// Returns the color with the color space coordinates moved into the given gamut.
+    ///
+    /// The brightness of the color is not changed.
+    pub fn clamp_to(self, gamut: &Gamut) -> Self {
+        Self {
+            space_coordinates: gamut.clamp(self.space_coordinates),
+            ..self
+        }
+    }
+
+    /// Returns the color space coordinates of the color.
+    pub fn space_coordinates(&self) -> (f32, f32) {
+        self.space_coordinates
+    }
+
+    /// Returns the brightness of the color.
+    pub fn brightness(&self) -> Option<u8> {
+        self.brightness
+    }
+
+    /// Converts the color into rgb values.
+    ///
+    /// If the color has no brightness, the maximum brightness is used.
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        let (x, y) = self.space_coordinates;
+        let brightness = self.brightness.unwrap_or(255) as f32 / 255.0;
+        if y <= 0.0 || brightness <= 0.0 {
+            return (0, 0, 0);
+        }
+        let x_value = brightness / y * x;
+        let z_value = brightness / y * (1.0 - x - y);
+        let red = x_value * 1.611_757 - brightness * 0.202_805 - z_value * 0.302_298;
+        let green = -x_value * 0.509_057 + brightness * 1.411_914 + z_value * 0.066_07;
+        let blue = x_value * 0.026_086 - brightness * 0.072_353 + z_value * 0.962_086;
+        let max = red.max(green).max(blue);
+        let scale = if max > 1.0 { max } else { 1.0 };
+        let gamma_correct = |v: f32| {
+            let v = (v / scale).max(0.0);
+            let v = if v <= 0.003_130_8 {
+                12.92 * v
+            } else {
+                (1.0 + 0.055) * v.powf(1.0 / 2.4) - 0.055
+            };
+            (v * 255.0).round().clamp(0.0, 255.0) as u8
+        };
+        (
+            gamma_correct(red),
+            gamma_correct(green),
+            gamma_correct(blue),
+        )
+    }
+
+    /// Converts the color into a hex string.
+    ///
+    /// The string begins with a `#` followed by 6 uppercase hexadecimal digits.
+    pub fn to_hex(&self) -> String {
+        let (red, green, blue) = self.to_rgb();
+        format!("#{:02X}{:02X}{:02X}", red, green, blue)
+    }
+
+    /// Converts the color into hue, saturation and value.
+    ///
+    /// The hue is returned in degrees, saturation and value are between 0 and 1.
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let (red, green, blue) = self.to_rgb();
+        let red = red as f32 / 255.0;
+        let green = green as f32 / 255.0;
+        let blue = blue as f32 / 255.0;
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let delta = max - min;
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == red {
+            60.0 * ((green - blue) / delta).rem_euclid(6.0)
+        } else if max == green {
+            60.0 * ((blue - red) / delta + 2.0)
+        } else {
+            60.0 * ((red - green) / delta + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+        (hue, saturation, max)
+    }
+
+    /// Converts the color into hue, saturation and lightness.
+    ///
+    /// The hue is returned in degrees, saturation and lightness are between 0 and 1.
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (hue, saturation, value) = self.to_hsv();
+        let lightness = value * (1.0 - saturation / 2.0);
+        let saturation = match lightness > 0.0 && lightness < 1.0 {
+            true => (value - lightness) / lightness.min(1.0 - lightness),
+            false => 0.0,
+        };
+        (hue, saturation, lightness)
+    }
+}
+
+/// Color gamut of a light.
+///
+/// A gamut is a triangle in the color space that contains all colors a light can display.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gamut {
+    /// Color space coordinates of the red corner.
+    pub red: (f32, f32),
+    /// Color space coordinates of the green corner.
+    pub green: (f32, f32),
+    /// Color space coordinates of the blue corner.
+    pub blue: (f32, f32),
+}
+
+impl Gamut {
+    /// Gamut of older color lights, for example LivingColors.
+    pub const A: Self = Self {
+        red: (0.704, 0.296),
+        green: (0.2151, 0.7106),
+        blue: (0.138, 0.08),
+    };
+    /// Gamut of the first generation of Hue bulbs.
+    pub const B: Self = Self {
+        red: (0.675, 0.322),
+        green: (0.409, 0.518),
+        blue: (0.167, 0.04),
+    };
+    /// Gamut of newer Hue bulbs.
+    pub const C: Self = Self {
+        red: (0.6915, 0.3083),
+        green: (0.17, 0.7),
+        blue: (0.1532, 0.0475),
+    };
+
+    /// Creates a new gamut from the corners of the triangle.
+    pub fn new(red: (f32, f32), green: (f32, f32), blue: (f32, f32)) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Returns the gamut from the control capabilities of a light.
+    ///
+    /// The corners of the gamut are preferred over the type of the gamut. Returns `None` if the
+    /// light has no known gamut.
+    pub fn from_capabilities(capabilities: &light::ControlCapabilities) -> Option<Self> {
+        if let Some(corners) = &capabilities.color_gamut {
+            if let [red, green, blue] = corners.as_slice() {
+                return Some(Self::new(*red, *green, *blue));
+            }
+        }
+        match capabilities.color_gamut_type.as_deref() {
+            Some("A") => Some(Self::A),
+            Some("B") => Some(Self::B),
+            Some("C") => Some(Self::C),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the gamut contains the given color space coordinates.
+    pub fn contains(&self, space_coordinates: (f32, f32)) -> bool {
+        let cross = |a: (f32, f32), b: (f32, f32), p: (f32, f32)| {
+            (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+        };
+        let d1 = cross(self.red, self.green, space_coordinates);
+        let d2 = cross(self.green, self.blue, space_coordinates);
+        let d3 = cross(self.blue, self.red, space_coordinates);
+        // Points on the edges are considered inside of the gamut.
+        let epsilon = 1e-6;
+        let has_negative = d1 < -epsilon || d2 < -epsilon || d3 < -epsilon;
+        let has_positive = d1 > epsilon || d2 > epsilon || d3 > epsilon;
+        !(has_negative && has_positive)
+    }
+
+    /// Returns the closest color space coordinates that are inside of the gamut.
+    pub fn clamp(&self, space_coordinates: (f32, f32)) -> (f32, f32) {
+        if self.contains(space_coordinates) {
+            return space_coordinates;
+        }
+        let closest_on_line = |a: (f32, f32), b: (f32, f32)| {
+            let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+            let t = ((space_coordinates.0 - a.0) * dx + (space_coordinates.1 - a.1) * dy)
+                / (dx * dx + dy * dy);
+            let t = t.clamp(0.0, 1.0);
+            (a.0 + t * dx, a.1 + t * dy)
+        };
+        let distance = |p: (f32, f32)| {
+            (p.0 - space_coordinates.0).powi(2) + (p.1 - space_coordinates.1).powi(2)
+        };
+        [
+            closest_on_line(self.red, self.green),
+            closest_on_line(self.green, self.blue),
+            closest_on_line(self.blue, self.red),
+        ]
+        .iter()
+        .copied()
+        .fold(None, |closest: Option<(f32, f32)>, v| match closest {
+            Some(c) if distance(c) <= distance(v) => Some(c),
+            _ => Some(v),
+        })
+        .unwrap_or(space_coordinates)
+    }
+}
+
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> (u8, u8, u8) {
+    let hue = hue.rem_euclid(360.0);
+    let saturation = saturation.clamp(0.0, 1.0);
+    let value = value.clamp(0.0, 1.0);
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let (red, green, blue) = match (hue / 60.0) as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    let to_u8 = |v: f32| ((v + m) * 255.0).round() as u8;
+    (to_u8(red), to_u8(green), to_u8(blue))
+}
+
+/// Color temperature of a light.
+///
+/// The Philips Hue API uses the mired scale, where 153 is the coldest (6500K) and 500 is the
+/// warmest (2000K) color temperature.
+///
+/// # Examples
+///
+/// Set a color temperature given in Kelvin:
+/// ```
+/// use huelib::{color::ColorTemperature, resource::light};
+///
+/// let modifier = light::StateModifier::new()
+///     .with_color_temperature(ColorTemperature::from_kelvin(2700));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ColorTemperature(u16);
+
+impl ColorTemperature {
+    /// Creates a new color temperature from a mired value.
+    pub fn from_mired(value: u16) -> Self {
+        Self(value)
+    }
+
+    /// Creates a new color temperature from a value in Kelvin.
+    pub fn from_kelvin(value: u16) -> Self {
+        let mired = (1_000_000.0 / value.max(1) as f64).round();
+        Self(mired.min(u16::MAX as f64) as u16)
+    }
+
+    /// Returns the color temperature in mired.
+    pub fn mired(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns the color temperature in Kelvin.
+    pub fn kelvin(&self) -> u16 {
+        let kelvin = (1_000_000.0 / self.0.max(1) as f64).round();
+        kelvin.min(u16::MAX as f64) as u16
+    }
+
+    /// Returns the color temperature clamped to the range that is supported by a light.
+    pub fn clamp_to(self, capabilities: &light::ColorTemperatureCapabilities) -> Self {
+        let min = capabilities.min.min(u16::MAX as usize) as u16;
+        let max = capabilities.max.min(u16::MAX as usize) as u16;
+        Self(self.0.max(min).min(max))
+    }
+
+    /// Converts the color temperature into a color with the approximate color space
+    /// coordinates.
+    ///
+    /// The returned color has no brightness.
+    pub fn to_color(&self) -> Color {
+        let (x, y) = color_temperature_to_space_coordinates(self.0);
+        Color::from_space_coordinates(x, y)
+    }
+
+    /// Converts the color temperature into approximate rgb values.
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        self.to_color().to_rgb()
+    }
+}
+
+impl From<u16> for ColorTemperature {
+    fn from(value: u16) -> Self {
+        Self::from_mired(value)
+    }
+}
+
+impl From<ColorTemperature> for Color {
+    fn from(value: ColorTemperature) -> Self {
+        value.to_color()
+    }
+}
+
+impl From<ColorTemperature> for Adjust<u16> {
+    fn from(value: ColorTemperature) -> Self {
+        Adjust::Override(value.0)
+    }
+}
+
+/// Approximates the color space coordinates of a mired color temperature on the Planckian locus.
+fn color_temperature_to_space_coordinates(mired: u16) -> (f32, f32) {
+    // NOTE: More information: https://en.wikipedia.org/wiki/Planckian_locus#Approximation
+    let kelvin = (1_000_000.0 / mired.max(1) as f64).clamp(1667.0, 25000.0);
+    let x = if kelvin <= 4000.0 {
+        -0.266_123_9e9 / kelvin.powi(3) - 0.234_358_9e6 / kelvin.powi(2)
+            + 0.877_695_6e3 / kelvin
+            + 0.179_910
+    } else {
+        -3.025_846_9e9 / kelvin.powi(3)
+            + 2.107_037_9e6 / kelvin.powi(2)
+            + 0.222_634_7e3 / kelvin
+            + 0.240_390
+    };
+    let y = if kelvin <= 2222.0 {
+        -1.106_381_4 * x.powi(3) - 1.348_110_20 * x.powi(2) + 2.185_558_32 * x - 0.202_196_83
+    } else if kelvin <= 4000.0 {
+        -0.954_947_6 * x.powi(3) - 1.374_185_93 * x.powi(2) + 2.091_370_15 * x - 0.167_488_67
+    } else {
+        3.081_758_0 * x.powi(3) - 5.873_386_70 * x.powi(2) + 3.751_129_97 * x - 0.370_014_83
+    };
+    (x as f32, y as f32)
 }
 
 /// Errors that can occur while parsing a hex string to a color.
@@ -123,11 +597,188 @@ pub enum ParseHexError {
     /// Error that occurs when the length of the hex string is invalid.
     #[error("Invalid string length")]
     InvalidLenght,
+    /// Error that occurs when the hex string does not start with a `#` or is not ascii.
+    #[error("Invalid string format")]
+    InvalidFormat,
     /// Error that can occur while parsing a int value.
     #[error("Failed to parse a int value")]
     ParseInt(#[from] ParseIntError),
 }
 
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_css(s)
+    }
+}
+
+/// Errors that can occur while parsing a CSS color string to a color.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ParseColorError {
+    /// Error that can occur while parsing a hex color.
+    #[error("Failed to parse a hex color: {0}")]
+    Hex(#[from] ParseHexError),
+    /// Error that occurs when the color name is unknown.
+    #[error("Unknown color name '{0}'")]
+    UnknownName(String),
+    /// Error that occurs when a color function is unknown or has invalid arguments.
+    #[error("Invalid color function '{0}'")]
+    InvalidFunction(String),
+}
+
+/// Named colors from the CSS specification, sorted by name.
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +832,139 @@ mod tests {
         assert_eq!(color, Color::from_hex("#112233").unwrap());
     }
 
+    #[test]
+    fn hex_invalid() {
+        assert!(Color::from_hex("#FF0000ZZ").is_err());
+        assert!(Color::from_hex("#f00z").is_err());
+        assert_eq!(Color::from_hex("#éab"), Err(ParseHexError::InvalidFormat));
+        assert_eq!(
+            Color::from_hex("FF00000"),
+            Err(ParseHexError::InvalidFormat)
+        );
+        assert_eq!(Color::from_hex("#FF000080"), Color::from_hex("#FF0000"));
+        assert!("#éab".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn convert_to_rgb() {
+        for (red, green, blue) in [(255, 0, 0), (0, 255, 0), (200, 100, 50), (255, 255, 255)] {
+            let (r, g, b) = Color::from_rgb(red, green, blue).to_rgb();
+            assert!((r as i16 - red as i16).abs() <= 2);
+            assert!((g as i16 - green as i16).abs() <= 2);
+            assert!((b as i16 - blue as i16).abs() <= 2);
+        }
+        assert_eq!(Color::from_rgb(0, 0, 0).to_rgb(), (0, 0, 0));
+        assert_eq!(Color::from_hex("#FFFFFF").unwrap().to_hex(), "#FFFFFF");
+    }
+
+    #[test]
+    fn convert_to_hsv_and_hsl() {
+        let (hue, saturation, value) = Color::from_hsv(120.0, 1.0, 1.0).to_hsv();
+        assert!((hue - 120.0).abs() < 2.0);
+        assert!(saturation > 0.98);
+        assert!(value > 0.98);
+        let (hue, saturation, lightness) = Color::from_hsl(120.0, 1.0, 0.5).to_hsl();
+        assert!((hue - 120.0).abs() < 2.0);
+        assert!(saturation > 0.98);
+        assert!((lightness - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn light_state() {
+        let mut state = light::State {
+            on: Some(true),
+            brightness: Some(100),
+            hue: Some(0),
+            saturation: Some(254),
+            color_space_coordinates: Some((0.3, 0.4)),
+            color_temperature: Some(153),
+            alert: None,
+            effect: None,
+            color_mode: Some(ColorMode::ColorSpaceCoordinates),
+            reachable: true,
+        };
+        let color = Color::from_light_state(&state).unwrap();
+        assert_eq!(color.space_coordinates(), (0.3, 0.4));
+        assert_eq!(color.brightness(), Some(100));
+
+        state.color_mode = Some(ColorMode::HueAndSaturation);
+        let color = Color::from_light_state(&state).unwrap();
+        let (red, green, blue) = color.to_rgb();
+        assert!(red > 200 && green < 10 && blue < 10);
+
+        state.color_mode = Some(ColorMode::ColorTemperature);
+        let (x, y) = Color::from_light_state(&state).unwrap().space_coordinates();
+        assert!((x - 0.313).abs() < 0.005 && (y - 0.324).abs() < 0.005);
+
+        state.color_mode = None;
+        assert_eq!(Color::from_light_state(&state), None);
+    }
+
+    #[test]
+    fn gamut() {
+        assert!(Gamut::C.contains((0.3, 0.3)));
+        assert!(!Gamut::B.contains((0.17, 0.7)));
+        assert_eq!(Gamut::C.clamp((0.3, 0.3)), (0.3, 0.3));
+        let (x, y) = Gamut::B.clamp((0.8, 0.2));
+        assert!((x - Gamut::B.red.0).abs() < 1e-4 && (y - Gamut::B.red.1).abs() < 1e-4);
+        let clamped = Gamut::A.clamp((0.4, 0.0));
+        assert!(Gamut::A.contains(clamped));
+        assert!(clamped.1 > 0.0);
+
+        let color = Color::from_rgb(0, 255, 0).clamp_to(&Gamut::B);
+        assert!(Gamut::B.contains(color.space_coordinates()));
+        assert_eq!(color.brightness(), Color::from_rgb(0, 255, 0).brightness());
+    }
+
+    #[test]
+    fn color_temperature() {
+        let value = ColorTemperature::from_kelvin(2000);
+        assert_eq!(value.mired(), 500);
+        assert_eq!(ColorTemperature::from_mired(153).kelvin(), 6536);
+        let capabilities = light::ColorTemperatureCapabilities { min: 153, max: 454 };
+        assert_eq!(value.clamp_to(&capabilities).mired(), 454);
+        assert_eq!(
+            ColorTemperature::from_mired(100).clamp_to(&capabilities),
+            ColorTemperature::from_mired(153)
+        );
+        let (red, _, blue) = value.to_rgb();
+        assert!(red > blue);
+        let (red, _, blue) = ColorTemperature::from_kelvin(10000).to_rgb();
+        assert!(blue > red);
+        assert_eq!(
+            Adjust::from(ColorTemperature::from_mired(300)),
+            Adjust::Override(300)
+        );
+    }
+
+    #[test]
+    fn parse_css() {
+        let red = Color::from_rgb(255, 0, 0);
+        assert_eq!(Color::from_css("red"), Ok(red));
+        assert_eq!(Color::from_css(" Red "), Ok(red));
+        assert_eq!(Color::from_css("#FF000080"), Ok(red));
+        assert_eq!(Color::from_css("#f008"), Ok(red));
+        assert_eq!(Color::from_css("rgb(255, 0, 0)"), Ok(red));
+        assert_eq!(Color::from_css("rgba(100%, 0%, 0%, 0.5)"), Ok(red));
+        assert_eq!(Color::from_css("rgb(255 0 0 / 50%)"), Ok(red));
+        assert_eq!(Color::from_css("hsl(0, 100%, 50%)"), Ok(red));
+        assert_eq!(Color::from_css("hsla(360deg 100% 50% / 1)"), Ok(red));
+        assert_eq!("#ff0000".parse(), Ok(red));
+        assert_eq!(
+            Color::from_css("unknown"),
+            Err(ParseColorError::UnknownName("unknown".to_owned()))
+        );
+        assert_eq!(
+            Color::from_css("rgb(1, 2)"),
+            Err(ParseColorError::InvalidFunction("rgb(1, 2)".to_owned()))
+        );
+        assert_eq!(
+            Color::from_css("hsl(0, 1, 2)"),
+            Err(ParseColorError::InvalidFunction("hsl(0, 1, 2)".to_owned()))
+        );
+        assert!(NAMED_COLORS.windows(2).all(|v| v[0].0 < v[1].0));
+    }
+
     #[test]
     fn rgb_and_hex() {
         let color1 = Color::from_hex("#fff").unwrap();
@@ -0,0 +1,1230 @@
+use crate::color::ColorTemperature;
+use crate::resource::{
+    group::Location, light, Adjust, Brightness, ColorMode, Group, TransitionTime,
+};
+use crate::{palette, util, Bridge, Color, Result};
+use derive_setters::Setters;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// States of lights that are sent at once, followed by a pause.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    /// The states of the lights, together with the identifiers of the lights.
+    pub states: Vec<(String, light::StateModifier)>,
+    /// Time until the next frame.
+    ///
+    /// An [`Engine`] plays every frame for at least [`MIN_DURATION`](Self::MIN_DURATION).
+    pub duration: Duration,
+}
+
+impl Frame {
+    /// The minimum duration of a frame that is played by an [`Engine`].
+    pub const MIN_DURATION: Duration = Duration::from_millis(100);
+}
+
+/// Trait for effects that are rendered locally.
+///
+/// An effect is a sequence of frames. The frames are sent to the bridge by an [`Engine`].
+pub trait Effect: Send {
+    /// Returns the identifiers of the lights that are changed by the effect.
+    fn lights(&self) -> Vec<String>;
+
+    /// Returns the next frame of the effect or `None` if the effect has ended.
+    fn next_frame(&mut self) -> Option<Frame>;
+}
+
+impl<E: Effect + ?Sized> Effect for Box<E> {
+    fn lights(&self) -> Vec<String> {
+        (**self).lights()
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        (**self).next_frame()
+    }
+}
+
+/// Engine that runs effects locally and sends the frames to a bridge.
+///
+/// The engine respects the command budget of the bridge by limiting the number of light states
+/// that are sent per second. When an effect ends or is stopped, the lights are restored to the
+/// state they had before the effect started.
+///
+/// Effects are played in real time. If an effect changes more light states per second than the
+/// rate allows, for example a [`Spatial`] effect with many lights and a short interval, the
+/// frames that can not be sent in time are skipped. Increase the interval of the effect or the
+/// rate of the engine to send every frame.
+///
+/// # Examples
+///
+/// Let a light breathe for ten seconds:
+/// ```no_run
+/// use huelib::effect::{Breathe, Engine};
+/// use std::{thread, time::Duration};
+///
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// let engine = Engine::new(bridge);
+/// let handle = engine.start(Breathe::new(vec!["1".into()]))?;
+/// thread::sleep(Duration::from_secs(10));
+/// handle.stop()?;
+/// # Ok::<(), huelib::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Engine {
+    bridge: Bridge,
+    rate: f64,
+}
+
+impl Engine {
+    /// Creates a new [`Engine`] that sends at most 10 light states per second.
+    pub fn new(bridge: Bridge) -> Self {
+        Self { bridge, rate: 10.0 }
+    }
+
+    /// Sets the maximum number of light states that are sent per second.
+    pub fn with_rate(self, rate: f64) -> Self {
+        Self {
+            rate: rate.max(0.1),
+            ..self
+        }
+    }
+
+    /// Returns the maximum number of light states that are sent per second.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Starts an effect in a new thread.
+    ///
+    /// The current states of the lights of the effect are fetched before the effect starts and
+    /// are restored when it ends. All lights are restored even if sending a state fails. The
+    /// handle returns the first error of the effect or, if the effect succeeded, the first error
+    /// of the restore.
+    pub fn start<E>(&self, mut effect: E) -> Result<Handle>
+    where
+        E: Effect + 'static,
+    {
+        let mut previous_states = Vec::new();
+        for id in effect.lights() {
+            let light = self.bridge.get_light(id.as_str())?;
+            previous_states.push((id, restore_modifier(&light.state)));
+        }
+        let bridge = self.bridge.clone();
+        let mut bucket = TokenBucket::new(self.rate, Instant::now());
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        let thread = thread::spawn(move || {
+            let mut result = run(&mut effect, &stopped, &SystemClock, |id, modifier| {
+                send(&bridge, &mut bucket, id, modifier)
+            });
+            // Every light is restored, even if the effect or another light failed.
+            for (id, modifier) in &previous_states {
+                let restored = send(&bridge, &mut bucket, id, modifier);
+                if result.is_ok() {
+                    result = restored;
+                }
+            }
+            result
+        });
+        Ok(Handle {
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+/// Sends the frames of an effect until it ends or is stopped.
+///
+/// Frames that already ended when they are due to be sent are skipped, so that the effect stays
+/// in sync with the wall-clock time. The last frame of an effect is never skipped. Frames shorter
+/// than [`Frame::MIN_DURATION`] are extended, so that effects with a duration of zero do not spin.
+fn run<E, C, F>(effect: &mut E, stopped: &AtomicBool, clock: &C, mut send: F) -> Result<()>
+where
+    E: Effect,
+    C: Clock,
+    F: FnMut(&str, &light::StateModifier) -> Result<()>,
+{
+    let mut deadline = clock.now();
+    let mut next = effect.next_frame();
+    while !stopped.load(Ordering::Relaxed) {
+        let frame = match next.take() {
+            Some(v) => v,
+            None => break,
+        };
+        next = effect.next_frame();
+        deadline += frame.duration.max(Frame::MIN_DURATION);
+        if clock.now() >= deadline && next.is_some() {
+            continue;
+        }
+        for (id, modifier) in &frame.states {
+            if stopped.load(Ordering::Relaxed) {
+                break;
+            }
+            send(id, modifier)?;
+        }
+        while !stopped.load(Ordering::Relaxed) {
+            let now = clock.now();
+            if now >= deadline {
+                break;
+            }
+            clock.sleep((deadline - now).min(Duration::from_millis(50)));
+        }
+    }
+    Ok(())
+}
+
+/// Source of the time that effects are played in.
+trait Clock {
+    fn now(&self) -> Instant;
+
+    fn sleep(&self, duration: Duration);
+}
+
+/// Clock that uses the time of the system.
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Sends the state of a light as soon as the command budget allows it.
+fn send(
+    bridge: &Bridge,
+    bucket: &mut TokenBucket,
+    id: &str,
+    modifier: &light::StateModifier,
+) -> Result<()> {
+    thread::sleep(bucket.delay(Instant::now()));
+    for response in bridge.set_light_state(id, modifier)? {
+        response.into_result()?;
+    }
+    Ok(())
+}
+
+/// Handle of an effect that is running.
+///
+/// Dropping the handle does not stop the effect.
+#[derive(Debug)]
+pub struct Handle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<Result<()>>>,
+}
+
+impl Handle {
+    /// Stops the effect and waits until the previous states of the lights are restored.
+    pub fn stop(mut self) -> Result<()> {
+        self.stop.store(true, Ordering::Relaxed);
+        self.join()
+    }
+
+    /// Waits until the effect has ended and the previous states of the lights are restored.
+    pub fn wait(mut self) -> Result<()> {
+        self.join()
+    }
+
+    /// Returns whether the effect has ended.
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(|v| v.is_finished())
+    }
+
+    fn join(&mut self) -> Result<()> {
+        match self.thread.take() {
+            Some(thread) => thread
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Limits the rate of commands that are sent to a bridge.
+#[derive(Clone, Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, now: Instant) -> Self {
+        Self {
+            rate,
+            tokens: rate,
+            last: now,
+        }
+    }
+
+    /// Takes a token and returns how long to wait before the command may be sent.
+    fn delay(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate) - 1.0;
+        self.last = now;
+        match self.tokens < 0.0 {
+            true => Duration::from_secs_f64(-self.tokens / self.rate),
+            false => Duration::from_secs(0),
+        }
+    }
+}
+
+/// Returns a modifier that sets a light back to the given state.
+///
+/// Lights that were off are only turned off, because the bridge does not accept other attributes
+/// for lights that are off.
+fn restore_modifier(state: &light::State) -> light::StateModifier {
+    if state.on == Some(false) {
+        return light::StateModifier::new().with_on(false);
+    }
+    let mut modifier = light::StateModifier {
+        on: state.on,
+        brightness: state.brightness.map(Adjust::Override),
+        ..Default::default()
+    };
+    match state.color_mode {
+        Some(ColorMode::ColorSpaceCoordinates) => {
+            modifier.color_space_coordinates = state.color_space_coordinates.map(Adjust::Override);
+        }
+        Some(ColorMode::HueAndSaturation) => {
+            modifier.hue = state.hue.map(Adjust::Override);
+            modifier.saturation = state.saturation.map(Adjust::Override);
+        }
+        Some(ColorMode::ColorTemperature) => {
+            modifier.color_temperature = state.color_temperature.map(Adjust::Override);
+        }
+        None => {}
+    }
+    modifier
+}
+
+/// Returns the transition time for a change that lasts the given duration.
+///
+/// Changes that last longer than the maximum transition time use the maximum transition time.
+fn transition_time(duration: Duration) -> TransitionTime {
+    TransitionTime::from_duration(duration).unwrap_or(TransitionTime::MAX)
+}
+
+fn frame<I>(lights: I, modifier: light::StateModifier, duration: Duration) -> Frame
+where
+    I: IntoIterator<Item = String>,
+{
+    Frame {
+        states: lights
+            .into_iter()
+            .map(|id| (id, modifier.clone()))
+            .collect(),
+        duration,
+    }
+}
+
+/// Effect that plays effects one after another.
+pub struct Sequence {
+    effects: Vec<Box<dyn Effect>>,
+}
+
+impl Sequence {
+    /// Creates a new [`Sequence`] from the given effects.
+    pub fn new(effects: Vec<Box<dyn Effect>>) -> Self {
+        Self { effects }
+    }
+}
+
+impl std::fmt::Debug for Sequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sequence")
+            .field("effects", &self.effects.len())
+            .finish()
+    }
+}
+
+impl Effect for Sequence {
+    fn lights(&self) -> Vec<String> {
+        let mut lights: Vec<String> = self.effects.iter().flat_map(|v| v.lights()).collect();
+        lights.sort();
+        lights.dedup();
+        lights
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        while !self.effects.is_empty() {
+            if let Some(frame) = self.effects[0].next_frame() {
+                return Some(frame);
+            }
+            self.effects.remove(0);
+        }
+        None
+    }
+}
+
+/// Effect that plays effects at the same time.
+///
+/// The effects should change different lights. If a light is changed by multiple effects at the
+/// same time, the state of the last effect is sent last.
+pub struct Parallel {
+    effects: Vec<(Box<dyn Effect>, Duration)>,
+    elapsed: Duration,
+}
+
+impl Parallel {
+    /// Creates a new [`Parallel`] from the given effects.
+    pub fn new(effects: Vec<Box<dyn Effect>>) -> Self {
+        Self {
+            effects: effects
+                .into_iter()
+                .map(|v| (v, Duration::from_secs(0)))
+                .collect(),
+            elapsed: Duration::from_secs(0),
+        }
+    }
+}
+
+impl std::fmt::Debug for Parallel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Parallel")
+            .field("effects", &self.effects.len())
+            .field("elapsed", &self.elapsed)
+            .finish()
+    }
+}
+
+impl Effect for Parallel {
+    fn lights(&self) -> Vec<String> {
+        let mut lights: Vec<String> = self.effects.iter().flat_map(|v| v.0.lights()).collect();
+        lights.sort();
+        lights.dedup();
+        lights
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        let mut states = Vec::new();
+        let elapsed = self.elapsed;
+        self.effects.retain_mut(|(effect, due)| {
+            if *due > elapsed {
+                return true;
+            }
+            match effect.next_frame() {
+                Some(frame) => {
+                    states.extend(frame.states);
+                    *due = elapsed + frame.duration;
+                    true
+                }
+                None => false,
+            }
+        });
+        let next = self.effects.iter().map(|v| v.1).min()?;
+        self.elapsed = next;
+        Some(Frame {
+            states,
+            duration: next - elapsed,
+        })
+    }
+}
+
+/// Effect that slowly dims lights up and down.
+#[derive(Clone, Debug, PartialEq, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct Breathe {
+    /// Identifiers of the lights.
+    #[setters(skip)]
+    pub lights: Vec<String>,
+    /// Duration of one breath. Defaults to 4 seconds.
+    pub period: Duration,
+    /// Lowest brightness. Defaults to 10% perceptual brightness.
+    pub min: Brightness,
+    /// Highest brightness. Defaults to the maximum brightness.
+    pub max: Brightness,
+    /// Number of breaths or `None` to breathe until the effect is stopped.
+    pub cycles: Option<u32>,
+    #[setters(skip)]
+    frame: u64,
+}
+
+impl Breathe {
+    /// Creates a new [`Breathe`] effect.
+    pub fn new(lights: Vec<String>) -> Self {
+        Self {
+            lights,
+            period: Duration::from_secs(4),
+            min: Brightness::from_perceptual(10.0),
+            max: Brightness::MAX,
+            cycles: None,
+            frame: 0,
+        }
+    }
+}
+
+impl Effect for Breathe {
+    fn lights(&self) -> Vec<String> {
+        self.lights.clone()
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        if let Some(cycles) = self.cycles {
+            if self.frame >= cycles as u64 * 2 {
+                return None;
+            }
+        }
+        let half = self.period / 2;
+        let brightness = match self.frame % 2 {
+            0 => self.max,
+            _ => self.min,
+        };
+        self.frame += 1;
+        let modifier = light::StateModifier::new()
+            .with_on(true)
+            .with_brightness(brightness)
+            .with_transition_time(transition_time(half));
+        Some(frame(self.lights.clone(), modifier, half))
+    }
+}
+
+/// Effect that lets lights flicker like a candle.
+#[derive(Clone, Debug, PartialEq, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct Candle {
+    /// Identifiers of the lights.
+    #[setters(skip)]
+    pub lights: Vec<String>,
+    /// Brightness of the flame. Defaults to 60% perceptual brightness.
+    pub brightness: Brightness,
+    /// Color temperature of the flame. Defaults to 2000K.
+    pub color_temperature: ColorTemperature,
+    /// Duration of the effect or `None` to flicker until the effect is stopped.
+    pub duration: Option<Duration>,
+    /// Seed of the random number generator.
+    pub seed: u64,
+    #[setters(skip)]
+    elapsed: Duration,
+}
+
+impl Candle {
+    /// Creates a new [`Candle`] effect.
+    pub fn new(lights: Vec<String>) -> Self {
+        Self {
+            lights,
+            brightness: Brightness::from_perceptual(60.0),
+            color_temperature: ColorTemperature::from_kelvin(2000),
+            duration: None,
+            seed: 0x2545_f491_4f6c_dd1d,
+            elapsed: Duration::from_secs(0),
+        }
+    }
+
+    /// Returns a random number between 0 and 1 using a linear congruential generator.
+    fn random(&mut self) -> f32 {
+        self.seed = self
+            .seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.seed >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+impl Effect for Candle {
+    fn lights(&self) -> Vec<String> {
+        self.lights.clone()
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        if let Some(duration) = self.duration {
+            if self.elapsed >= duration {
+                return None;
+            }
+        }
+        let duration = Duration::from_millis(400 + (self.random() * 400.0) as u64);
+        let base = self.brightness.perceptual();
+        let lights = self.lights.clone();
+        let states = lights
+            .into_iter()
+            .map(|id| {
+                let brightness = base * (0.6 + 0.4 * self.random());
+                let modifier = light::StateModifier::new()
+                    .with_on(true)
+                    .with_brightness(Brightness::from_perceptual(brightness))
+                    .with_color_temperature(self.color_temperature)
+                    .with_transition_time(transition_time(duration));
+                (id, modifier)
+            })
+            .collect();
+        self.elapsed += duration;
+        Some(Frame { states, duration })
+    }
+}
+
+/// Effect that slowly fades lights from a dim red to a bright warm white.
+#[derive(Clone, Debug, PartialEq, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct Sunrise {
+    /// Identifiers of the lights.
+    #[setters(skip)]
+    pub lights: Vec<String>,
+    /// Duration of the sunrise. Defaults to 15 minutes.
+    pub duration: Duration,
+    /// Number of steps of the sunrise. Defaults to 30.
+    pub steps: u32,
+    #[setters(skip)]
+    step: u32,
+}
+
+impl Sunrise {
+    /// Creates a new [`Sunrise`] effect.
+    pub fn new(lights: Vec<String>) -> Self {
+        Self {
+            lights,
+            duration: Duration::from_secs(15 * 60),
+            steps: 30,
+            step: 0,
+        }
+    }
+
+    /// Returns the color of the sunrise at the given progress between 0 and 1.
+    fn color(progress: f32) -> (f32, f32) {
+        let keyframes = [
+            (0.0, (0.675, 0.322)),
+            (0.4, (0.585, 0.39)),
+            (
+                1.0,
+                ColorTemperature::from_kelvin(2700)
+                    .to_color()
+                    .space_coordinates,
+            ),
+        ];
+        let index = keyframes
+            .iter()
+            .rposition(|v| v.0 <= progress)
+            .unwrap_or(0)
+            .min(keyframes.len() - 2);
+        let (start, end) = (keyframes[index], keyframes[index + 1]);
+        let t = ((progress - start.0) / (end.0 - start.0)).clamp(0.0, 1.0);
+        (
+            start.1 .0 + (end.1 .0 - start.1 .0) * t,
+            start.1 .1 + (end.1 .1 - start.1 .1) * t,
+        )
+    }
+}
+
+impl Effect for Sunrise {
+    fn lights(&self) -> Vec<String> {
+        self.lights.clone()
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        let steps = self.steps.max(1);
+        if self.step > steps {
+            return None;
+        }
+        let duration = self.duration / steps;
+        let modifier = match self.step {
+            0 => light::StateModifier::new()
+                .with_on(true)
+                .with_brightness(Brightness::MIN)
+                .with_color_space_coordinates(Adjust::Override(Self::color(0.0)))
+                .with_transition_time(TransitionTime::from_raw(0)),
+            step => {
+                let progress = step as f32 / steps as f32;
+                light::StateModifier::new()
+                    .with_on(true)
+                    .with_brightness(Brightness::from_perceptual(progress * 100.0))
+                    .with_color_space_coordinates(Adjust::Override(Self::color(progress)))
+                    .with_transition_time(transition_time(duration))
+            }
+        };
+        let duration = match self.step {
+            0 => Duration::from_millis(500),
+            _ => duration,
+        };
+        self.step += 1;
+        Some(frame(self.lights.clone(), modifier, duration))
+    }
+}
+
+/// Effect that cycles the lights through a list of colors.
+///
+/// Every light is one color ahead of the previous light, so that the colors move across the
+/// lights of a group.
+#[derive(Clone, Debug, PartialEq, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct ColorCycle {
+    /// Identifiers of the lights.
+    #[setters(skip)]
+    pub lights: Vec<String>,
+    /// Colors to cycle through.
+    #[setters(skip)]
+    pub colors: Vec<Color>,
+    /// Duration of one color. Defaults to 5 seconds.
+    pub step: Duration,
+    /// Number of cycles through all colors or `None` to cycle until the effect is stopped.
+    pub cycles: Option<u32>,
+    #[setters(skip)]
+    frame: u64,
+}
+
+impl ColorCycle {
+    /// Creates a new [`ColorCycle`] effect.
+    pub fn new(lights: Vec<String>, colors: Vec<Color>) -> Self {
+        Self {
+            lights,
+            colors,
+            step: Duration::from_secs(5),
+            cycles: None,
+            frame: 0,
+        }
+    }
+}
+
+impl Effect for ColorCycle {
+    fn lights(&self) -> Vec<String> {
+        self.lights.clone()
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        if self.colors.is_empty() {
+            return None;
+        }
+        if let Some(cycles) = self.cycles {
+            if self.frame >= cycles as u64 * self.colors.len() as u64 {
+                return None;
+            }
+        }
+        let states = self
+            .lights
+            .iter()
+            .enumerate()
+            .map(|(index, id)| {
+                let color = self.colors[(self.frame as usize + index) % self.colors.len()];
+                let modifier = light::StateModifier::new()
+                    .with_on(true)
+                    .with_color(color)
+                    .with_transition_time(transition_time(self.step));
+                (id.clone(), modifier)
+            })
+            .collect();
+        self.frame += 1;
+        Some(Frame {
+            states,
+            duration: self.step,
+        })
+    }
+}
+
+/// Effect that pulses lights in a way that is safe for people with photosensitive epilepsy.
+///
+/// The period of a pulse is at least one second, the brightness never drops below 30% of the
+/// perceptual brightness and every change uses a smooth transition. This stays well below the
+/// limit of three flashes per second.
+#[derive(Clone, Debug, PartialEq, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct SafePulse {
+    /// Identifiers of the lights.
+    #[setters(skip)]
+    pub lights: Vec<String>,
+    /// Color of the pulse.
+    #[setters(skip)]
+    pub color: Color,
+    /// Number of pulses.
+    #[setters(skip)]
+    pub count: u32,
+    /// Duration of one pulse. Defaults to 2 seconds and is at least one second.
+    pub period: Duration,
+    #[setters(skip)]
+    frame: u64,
+}
+
+impl SafePulse {
+    /// The minimum period of a pulse.
+    pub const MIN_PERIOD: Duration = Duration::from_secs(1);
+
+    /// Creates a new [`SafePulse`] effect.
+    pub fn new(lights: Vec<String>, color: Color, count: u32) -> Self {
+        Self {
+            lights,
+            color,
+            count,
+            period: Duration::from_secs(2),
+            frame: 0,
+        }
+    }
+}
+
+impl Effect for SafePulse {
+    fn lights(&self) -> Vec<String> {
+        self.lights.clone()
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        if self.frame >= self.count as u64 * 2 {
+            return None;
+        }
+        let half = self.period.max(Self::MIN_PERIOD) / 2;
+        let brightness = match self.frame % 2 {
+            0 => Brightness::MAX,
+            _ => Brightness::from_perceptual(30.0),
+        };
+        self.frame += 1;
+        let modifier = light::StateModifier::new()
+            .with_on(true)
+            .with_color(self.color)
+            .with_brightness(brightness)
+            .with_transition_time(transition_time(half));
+        Some(frame(self.lights.clone(), modifier, half))
+    }
+}
+
+/// Pattern of a [`Spatial`] effect.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pattern {
+    /// A wave that sweeps from the left to the right.
+    Wave,
+    /// A pulse that spreads out from a point.
+    RadialPulse {
+        /// Point where the pulse starts.
+        center: Location,
+    },
+    /// A gradient from the back to the front of the room.
+    DepthGradient,
+}
+
+/// Effect that colors lights depending on their location in an entertainment group.
+///
+/// The states can either be sent as light states through an [`Engine`] or as messages for
+/// entertainment streaming with [`stream_message`](Self::stream_message).
+///
+/// # Examples
+///
+/// Let a wave sweep through an entertainment group:
+/// ```no_run
+/// use huelib::effect::{Engine, Spatial};
+/// use huelib::Color;
+///
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// let group = bridge.get_group("1")?;
+/// let wave = Spatial::wave(&group).with_color(Color::from_rgb(0, 0, 255));
+/// Engine::new(bridge).start(wave)?.wait()?;
+/// # Ok::<(), huelib::Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct Spatial {
+    /// Pattern of the effect.
+    #[setters(skip)]
+    pub pattern: Pattern,
+    /// Identifiers and locations of the lights.
+    #[setters(skip)]
+    pub locations: Vec<(String, Location)>,
+    /// Color of the pattern. Defaults to white.
+    pub color: Color,
+    /// Color of the lights outside of the pattern. Defaults to black, which turns the lights off.
+    pub background: Color,
+    /// Duration of one wave or pulse. Defaults to 4 seconds.
+    pub period: Duration,
+    /// Width of the wave or pulse in the coordinates of the locations. Defaults to 0.5.
+    pub width: f32,
+    /// Duration between two frames. Defaults to 200 milliseconds and is at least
+    /// [`Frame::MIN_DURATION`].
+    pub interval: Duration,
+    /// Duration of the effect or `None` to run until the effect is stopped.
+    pub duration: Option<Duration>,
+    #[setters(skip)]
+    elapsed: Duration,
+}
+
+impl Spatial {
+    /// Creates a new [`Spatial`] effect with the given pattern.
+    ///
+    /// Lights without a location are not changed by the effect. The lights are ordered by their
+    /// identifiers, numerically if possible.
+    pub fn new(group: &Group, pattern: Pattern) -> Self {
+        let mut locations: Vec<_> = group
+            .locations
+            .iter()
+            .flatten()
+            .map(|(id, location)| (id.clone(), *location))
+            .collect();
+        locations.sort_by(|a, b| util::compare_ids(&a.0, &b.0));
+        Self {
+            pattern,
+            locations,
+            color: Color::from_rgb(255, 255, 255),
+            background: Color::from_rgb(0, 0, 0),
+            period: Duration::from_secs(4),
+            width: 0.5,
+            interval: Duration::from_millis(200),
+            duration: None,
+            elapsed: Duration::default(),
+        }
+    }
+
+    /// Creates a new [`Spatial`] effect with a wave that sweeps from the left to the right.
+    pub fn wave(group: &Group) -> Self {
+        Self::new(group, Pattern::Wave)
+    }
+
+    /// Creates a new [`Spatial`] effect with a pulse that spreads out from a point.
+    pub fn radial_pulse(group: &Group, center: Location) -> Self {
+        Self::new(group, Pattern::RadialPulse { center })
+    }
+
+    /// Creates a new [`Spatial`] effect with a gradient from the back to the front of the room.
+    ///
+    /// The lights in the back have the background color and the lights in the front the color of
+    /// the effect.
+    pub fn depth_gradient(group: &Group) -> Self {
+        Self::new(group, Pattern::DepthGradient)
+    }
+
+    /// Returns the rgb values of the lights at the given time after the start of the effect.
+    pub fn colors_at(&self, elapsed: Duration) -> Vec<(String, (u8, u8, u8))> {
+        let period = self.period.as_secs_f32();
+        let phase = match period > 0.0 {
+            true => (elapsed.as_secs_f32() % period) / period,
+            false => 0.0,
+        };
+        let width = self.width.max(f32::EPSILON);
+        let pulse = |distance: f32| (1.0 - distance.abs() / width).max(0.0);
+        let radius = match self.pattern {
+            Pattern::RadialPulse { center } => {
+                let max = self
+                    .locations
+                    .iter()
+                    .map(|(_, location)| location.distance(&center))
+                    .fold(0.0, f32::max);
+                phase * (max + width)
+            }
+            _ => 0.0,
+        };
+        let (color, background) = (self.color.to_rgb(), self.background.to_rgb());
+        self.locations
+            .iter()
+            .map(|(id, location)| {
+                let intensity = match self.pattern {
+                    Pattern::Wave => {
+                        pulse(location.x - (-1.0 - width + phase * 2.0 * (1.0 + width)))
+                    }
+                    Pattern::RadialPulse { center } => pulse(location.distance(&center) - radius),
+                    Pattern::DepthGradient => ((location.y + 1.0) / 2.0).clamp(0.0, 1.0),
+                };
+                (
+                    id.clone(),
+                    palette::mix(background, color, intensity as f64),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the states of the lights at the given time after the start of the effect.
+    pub fn states_at(&self, elapsed: Duration) -> Vec<(String, light::StateModifier)> {
+        self.colors_at(elapsed)
+            .into_iter()
+            .map(|(id, rgb)| {
+                let modifier = match rgb {
+                    (0, 0, 0) => light::StateModifier::new().with_on(false),
+                    (red, green, blue) => light::StateModifier::new()
+                        .with_on(true)
+                        .with_color(Color::from_rgb(red, green, blue)),
+                };
+                (
+                    id,
+                    modifier.with_transition_time(transition_time(self.interval)),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns a message for entertainment streaming with the colors of the lights at the given
+    /// time after the start of the effect.
+    ///
+    /// The message uses version 1 of the HueStream protocol in the rgb color space. Lights with an
+    /// identifier that is not a number are skipped.
+    pub fn stream_message(&self, elapsed: Duration, sequence: u8) -> Vec<u8> {
+        let mut message = b"HueStream".to_vec();
+        message.extend_from_slice(&[0x01, 0x00, sequence, 0x00, 0x00, 0x00, 0x00]);
+        for (id, (red, green, blue)) in self.colors_at(elapsed) {
+            let id: u16 = match id.parse() {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+            message.push(0x00);
+            message.extend_from_slice(&id.to_be_bytes());
+            for value in &[red, green, blue] {
+                message.extend_from_slice(&(*value as u16 * 257).to_be_bytes());
+            }
+        }
+        message
+    }
+}
+
+impl Effect for Spatial {
+    fn lights(&self) -> Vec<String> {
+        self.locations.iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    fn next_frame(&mut self) -> Option<Frame> {
+        if self.locations.is_empty() || self.duration.is_some_and(|v| self.elapsed >= v) {
+            return None;
+        }
+        // The gradient does not move, so it is only refreshed once per period.
+        let step = match self.pattern {
+            Pattern::DepthGradient => self.period,
+            _ => self.interval,
+        }
+        .max(Frame::MIN_DURATION);
+        let states = self.states_at(self.elapsed);
+        self.elapsed += step;
+        Some(Frame {
+            states,
+            duration: step,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect<E: Effect>(mut effect: E, limit: usize) -> Vec<Frame> {
+        std::iter::from_fn(|| effect.next_frame())
+            .take(limit)
+            .collect()
+    }
+
+    #[test]
+    fn render_effects() {
+        let frames = collect(Breathe::new(vec!["1".into()]).with_cycles(2), 10);
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0].duration, Duration::from_secs(2));
+        assert_eq!(
+            frames[0].states[0].1.brightness,
+            Some(Adjust::Override(254))
+        );
+        assert_eq!(
+            frames[0].states[0].1.transition_time,
+            Some(TransitionTime::from_raw(20))
+        );
+
+        let candle = Candle::new(vec!["1".into(), "2".into()])
+            .with_duration(Duration::from_secs(5))
+            .with_seed(1);
+        let frames = collect(candle.clone(), 100);
+        assert!(frames.len() >= 6 && frames.len() <= 13);
+        assert_eq!(frames, collect(candle, 100));
+        assert!(frames
+            .iter()
+            .all(|v| v.duration >= Duration::from_millis(400)));
+
+        let frames = collect(
+            Sunrise::new(vec!["1".into()]).with_duration(Duration::from_secs(100)),
+            100,
+        );
+        assert_eq!(frames.len(), 31);
+        assert_eq!(frames[0].states[0].1.brightness, Some(Adjust::Override(1)));
+        assert_eq!(
+            frames[30].states[0].1.brightness,
+            Some(Adjust::Override(254))
+        );
+
+        let colors = vec![Color::from_rgb(255, 0, 0), Color::from_rgb(0, 0, 255)];
+        let cycle = ColorCycle::new(vec!["1".into(), "2".into()], colors).with_cycles(1);
+        let frames = collect(cycle, 10);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].states[0].1, frames[1].states[1].1);
+
+        let pulse = SafePulse::new(vec!["1".into()], Color::from_rgb(255, 0, 0), 3)
+            .with_period(Duration::from_millis(100));
+        let frames = collect(pulse, 10);
+        assert_eq!(frames.len(), 6);
+        assert!(frames
+            .iter()
+            .all(|v| v.duration >= SafePulse::MIN_PERIOD / 2));
+    }
+
+    #[test]
+    fn compose_effects() {
+        let breathe = Breathe::new(vec!["1".into()]).with_cycles(1);
+        let pulse = SafePulse::new(vec!["2".into()], Color::from_rgb(255, 0, 0), 2)
+            .with_period(Duration::from_secs(1));
+        let sequence = Sequence::new(vec![Box::new(breathe.clone()), Box::new(pulse.clone())]);
+        assert_eq!(sequence.lights(), vec!["1", "2"]);
+        assert_eq!(collect(sequence, 10).len(), 6);
+
+        let parallel = Parallel::new(vec![Box::new(breathe), Box::new(pulse)]);
+        let frames = collect(parallel, 10);
+        let durations: Vec<_> = frames.iter().map(|v| v.duration.as_millis()).collect();
+        assert_eq!(durations, vec![500, 500, 500, 500, 2000]);
+        let lights: Vec<_> = frames.iter().map(|v| v.states.len()).collect();
+        assert_eq!(lights, vec![2, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn restore_states() {
+        let state: light::State = serde_json::from_value(serde_json::json!({
+            "on": false,
+            "bri": 100,
+            "xy": [0.3, 0.3],
+            "colormode": "xy",
+            "reachable": true
+        }))
+        .unwrap();
+        assert_eq!(
+            restore_modifier(&state),
+            light::StateModifier::new().with_on(false)
+        );
+        let state = light::State {
+            on: Some(true),
+            ..state
+        };
+        let modifier = restore_modifier(&state);
+        assert_eq!(modifier.brightness, Some(Adjust::Override(100)));
+        assert_eq!(
+            modifier.color_space_coordinates,
+            Some(Adjust::Override((0.3, 0.3)))
+        );
+    }
+
+    /// Clock that only advances when it sleeps.
+    struct TestClock(std::cell::Cell<Instant>);
+
+    impl TestClock {
+        fn new() -> Self {
+            Self(std::cell::Cell::new(Instant::now()))
+        }
+    }
+
+    impl Clock for TestClock {
+        fn now(&self) -> Instant {
+            self.0.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.0.set(self.0.get() + duration);
+        }
+    }
+
+    #[test]
+    fn skip_frames() {
+        let mut effect = ColorCycle::new(
+            vec!["1".into()],
+            vec![Color::from_rgb(255, 0, 0), Color::from_rgb(0, 0, 255)],
+        )
+        .with_step(Duration::from_millis(100))
+        .with_cycles(5);
+        let last = collect(effect.clone(), 10).pop().unwrap().states.pop();
+        let stopped = AtomicBool::new(false);
+        let clock = TestClock::new();
+        let start = clock.now();
+        let mut sent = Vec::new();
+        run(&mut effect, &stopped, &clock, |_, modifier| {
+            sent.push(modifier.clone());
+            clock.sleep(Duration::from_millis(250));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(sent.len(), 5);
+        assert_eq!(sent.last(), last.as_ref().map(|v| &v.1));
+        assert_eq!(clock.now() - start, Duration::from_millis(1250));
+    }
+
+    #[test]
+    fn extend_short_frames() {
+        let mut effect = ColorCycle::new(vec!["1".into()], vec![Color::from_rgb(255, 0, 0)])
+            .with_step(Duration::from_secs(0));
+        let stopped = AtomicBool::new(false);
+        let clock = TestClock::new();
+        let start = clock.now();
+        let mut sent = 0;
+        run(&mut effect, &stopped, &clock, |_, _| {
+            sent += 1;
+            if sent == 3 {
+                stopped.store(true, Ordering::Relaxed);
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(sent, 3);
+        assert_eq!(clock.now() - start, Frame::MIN_DURATION * 2);
+
+        let group: Group = serde_json::from_value(serde_json::json!({
+            "name": "Entertainment area",
+            "lights": ["1"],
+            "type": "Entertainment",
+            "sensors": [],
+            "locations": {"1": [0.0, 0.0, 0.0]}
+        }))
+        .unwrap();
+        let wave = Spatial::wave(&group).with_interval(Duration::from_secs(0));
+        assert!(collect(wave, 3)
+            .iter()
+            .all(|v| v.duration == Frame::MIN_DURATION));
+    }
+
+    #[test]
+    fn limit_rate() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, start);
+        assert_eq!(bucket.delay(start), Duration::from_secs(0));
+        assert_eq!(bucket.delay(start), Duration::from_secs(0));
+        assert_eq!(bucket.delay(start), Duration::from_millis(500));
+        assert_eq!(bucket.delay(start), Duration::from_millis(1000));
+        let later = start + Duration::from_secs(10);
+        assert_eq!(bucket.delay(later), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn render_spatial() {
+        let group: Group = serde_json::from_value(serde_json::json!({
+            "name": "Entertainment area",
+            "lights": ["1", "2", "3"],
+            "type": "Entertainment",
+            "sensors": [],
+            "locations": {"1": [-1.0, -1.0, 0.0], "2": [0.0, 0.0, 0.0], "3": [1.0, 1.0]}
+        }))
+        .unwrap();
+        let white = (255, 255, 255);
+
+        let wave = Spatial::wave(&group).with_period(Duration::from_secs(3));
+        let colors = wave.colors_at(Duration::from_millis(500));
+        assert_eq!(colors[0], ("1".into(), white));
+        assert_eq!(colors[1].1, (0, 0, 0));
+        assert_eq!(wave.colors_at(Duration::from_millis(1500))[1].1, white);
+        let states = wave.states_at(Duration::from_millis(1500));
+        assert_eq!(states[0].1.on, Some(false));
+        assert_eq!(states[1].1.on, Some(true));
+
+        let pulse = Spatial::radial_pulse(&group, Location::new(0.0, 0.0, 0.0));
+        assert_eq!(pulse.colors_at(Duration::from_secs(0))[1].1, white);
+        assert_eq!(pulse.colors_at(Duration::from_secs(0))[0].1, (0, 0, 0));
+
+        let gradient = Spatial::depth_gradient(&group).with_duration(Duration::from_secs(8));
+        let colors = gradient.colors_at(Duration::from_secs(0));
+        assert_eq!(colors[0].1, (0, 0, 0));
+        assert_eq!(colors[2].1, white);
+        assert_eq!(collect(gradient, 10).len(), 2);
+
+        let message = wave.stream_message(Duration::from_millis(500), 7);
+        assert_eq!(message.len(), 16 + 3 * 9);
+        assert_eq!(&message[..9], b"HueStream");
+        assert_eq!(message[11], 7);
+        assert_eq!(&message[16..25], &[0, 0, 1, 255, 255, 255, 255, 255, 255]);
+
+        let group: Group = serde_json::from_value(serde_json::json!({
+            "name": "Entertainment area",
+            "lights": ["2", "10"],
+            "type": "Entertainment",
+            "sensors": [],
+            "locations": {"10": [1.0, 0.0, 0.0], "2": [-1.0, 0.0, 0.0]}
+        }))
+        .unwrap();
+        assert_eq!(Spatial::wave(&group).lights(), vec!["2", "10"]);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::automation::InvalidAutomationError;
+use crate::resource::sensor::ValidateError;
 use crate::response::Error as ResponseError;
 use chrono::ParseError as ChronoParseError;
 use serde_json::Error as SerdeJsonError;
@@ -49,6 +51,14 @@ pub enum Error {
     #[error("Failed to parse description")]
     ParseDescription(#[from] SerdeXmlError),
 
+    /// Error that occurs when an automation cannot be compiled into rules.
+    #[error("Invalid automation")]
+    InvalidAutomation(#[from] InvalidAutomationError),
+
+    /// Error that occurs when a sensor creator or modifier is not supported by the sensor.
+    #[error("Invalid sensor attributes")]
+    ValidateSensor(#[from] ValidateError),
+
     /// Error that is returned by the Philips Hue API.
     #[error("Error returned from Philips Hue API")]
     Response(#[from] ResponseError),
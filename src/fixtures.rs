@@ -0,0 +1,78 @@
+use crate::resource::{
+    rule::Condition,
+    schedule::{Command, CommandRequestMethod, Status},
+    Light, Rule, Schedule, Sensor,
+};
+use chrono::NaiveDateTime;
+use serde_json::{json, Value as JsonValue};
+
+/// Returns an extended color light that is on and reachable.
+pub(crate) fn light(id: &str, gamut_type: &str) -> Light {
+    let light: Light = serde_json::from_value(json!({
+        "name": "light",
+        "type": "Extended color light",
+        "state": {"on": true, "bri": 254, "reachable": true},
+        "modelid": "LCT015",
+        "uniqueid": "00:17:88:01:00:00:00:01-0b",
+        "swversion": "1.0",
+        "swupdate": {"state": "noupdates", "lastinstall": null},
+        "config": {"archetype": "sultanbulb", "function": "mixed", "direction": "omnidirectional"},
+        "capabilities": {
+            "certified": true,
+            "control": {"colorgamuttype": gamut_type},
+            "streaming": {"renderer": true, "proxy": true}
+        }
+    }))
+    .unwrap();
+    Light {
+        id: id.to_owned(),
+        ..light
+    }
+}
+
+/// Returns a dimmer switch with the given button event.
+pub(crate) fn switch(id: &str, button_event: u32, last_updated: &str) -> Sensor {
+    let sensor: Sensor = serde_json::from_value(json!({
+        "name": "Dimmer switch",
+        "type": "ZLLSwitch",
+        "modelid": "RWL021",
+        "state": {"buttonevent": button_event, "lastupdated": last_updated},
+        "config": {"on": true, "reachable": true, "battery": 100}
+    }))
+    .unwrap();
+    sensor.with_id(id.to_owned())
+}
+
+/// Returns an enabled schedule that turns on a light.
+pub(crate) fn schedule(local_time: &str, start_time: Option<NaiveDateTime>) -> Schedule {
+    Schedule {
+        id: "1".into(),
+        name: "test".into(),
+        description: String::new(),
+        command: Command {
+            address: "/api/user/lights/1/state".into(),
+            request_method: CommandRequestMethod::Put,
+            body: json!({"on": true}),
+        },
+        local_time: local_time.parse().unwrap(),
+        start_time,
+        status: Status::Enabled,
+        auto_delete: None,
+    }
+}
+
+/// Returns an enabled rule that sets the state of a group.
+pub(crate) fn rule(id: &str, conditions: Vec<Condition>, body: JsonValue) -> Rule {
+    serde_json::from_value::<Rule>(json!({
+        "name": format!("rule {}", id),
+        "owner": "user",
+        "lasttriggered": "none",
+        "timestriggered": 0,
+        "created": "2020-01-01T00:00:00",
+        "status": "enabled",
+        "conditions": conditions,
+        "actions": [{"address": "/groups/1/action", "method": "PUT", "body": body}]
+    }))
+    .unwrap()
+    .with_id(id.to_owned())
+}
@@ -17,10 +17,10 @@
 //! # Features
 //!
 //! - `upnp-description`: Adds support for accessing the UPnP description of a bridge. See the
-//! [`bridge::Description`] struct for more information.
+//!   [`bridge::Description`] struct for more information.
 //! - `old-api`: Minimal effort support for older api versions. Useful for users of the no longer
-//! supported Hue v1 bridge. This lowers the supported API version to `1.16` not all features
-//! are guarenteed to work.
+//!   supported Hue v1 bridge. This lowers the supported API version to `1.16` not all features
+//!   are guarenteed to work.
 //!
 //! # Connecting to a bridge
 //!
@@ -212,15 +212,27 @@
 #[macro_use]
 mod util;
 mod error;
+#[cfg(test)]
+mod fixtures;
 
+/// Module for compiling automations into bridge resources.
+pub mod automation;
 /// Module for managing bridges.
 pub mod bridge;
 /// Module for generating colors.
 pub mod color;
+/// Module for running light effects locally.
+pub mod effect;
+/// Module for generating color palettes from images, harmonies and gradients.
+pub mod palette;
+/// Module for rendering rules and schedules in a human-readable form.
+pub mod render;
 /// Module for bridge resources.
 pub mod resource;
 /// Responses returned from the Philips Hue API.
 pub mod response;
+/// Module for simulating the rule engine of a bridge.
+pub mod simulator;
 
 pub use bridge::Bridge;
 pub use color::Color;
@@ -0,0 +1,450 @@
+use crate::resource::{light, scene, Brightness, Light};
+use crate::Color;
+use std::collections::HashMap;
+
+/// Palette of dominant colors that were extracted from an image.
+///
+/// The colors are extracted with the median cut algorithm and sorted by how many pixels of the
+/// image they represent.
+///
+/// # Examples
+///
+/// Create a scene that looks like an image:
+/// ```no_run
+/// use huelib::palette::Palette;
+///
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// # let pixels: Vec<u8> = Vec::new();
+/// // The pixels of the image as a buffer of rgb values, for example from the `image` crate.
+/// let palette = Palette::from_rgb_bytes(&pixels, 5);
+/// let group = bridge.get_group("1")?;
+/// let lights = group
+///     .lights
+///     .iter()
+///     .map(|id| bridge.get_light(id.as_str()))
+///     .collect::<huelib::Result<Vec<_>>>()?;
+/// let creator = palette.scene_creator("Photo".into(), &lights);
+/// bridge.create_scene(&creator)?;
+/// # Ok::<(), huelib::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Palette {
+    colors: Vec<(u8, u8, u8)>,
+}
+
+impl Palette {
+    /// Extracts a palette with at most `count` colors from the pixels of an image.
+    pub fn from_pixels(pixels: &[(u8, u8, u8)], count: usize) -> Self {
+        let mut boxes = match pixels.is_empty() || count == 0 {
+            true => Vec::new(),
+            false => vec![pixels.to_vec()],
+        };
+        while boxes.len() < count {
+            let widest = boxes
+                .iter()
+                .enumerate()
+                .filter(|(_, pixels)| pixels.len() > 1)
+                .map(|(index, pixels)| {
+                    let (channel, range) = widest_channel(pixels);
+                    (index, channel, range)
+                })
+                .filter(|(_, _, range)| *range > 0)
+                .max_by_key(|(_, _, range)| *range);
+            let (index, channel) = match widest {
+                Some((index, channel, _)) => (index, channel),
+                None => break,
+            };
+            let mut pixels = boxes.swap_remove(index);
+            pixels.sort_unstable_by_key(|v| channel_value(*v, channel));
+            // Splits at the median without separating pixels with the same value.
+            let median = channel_value(pixels[pixels.len() / 2], channel);
+            let index = match pixels.partition_point(|v| channel_value(*v, channel) < median) {
+                0 => pixels.partition_point(|v| channel_value(*v, channel) <= median),
+                v => v,
+            };
+            let upper = pixels.split_off(index);
+            boxes.push(pixels);
+            boxes.push(upper);
+        }
+        boxes.sort_by_key(|pixels| std::cmp::Reverse(pixels.len()));
+        Self {
+            colors: boxes.iter().map(|pixels| average(pixels)).collect(),
+        }
+    }
+
+    /// Generates a palette with `count` colors from a base color and a harmony rule.
+    ///
+    /// The colors are generated in the Oklab color space, so that they are perceptually uniform.
+    /// The first color of the palette is always the base color.
+    ///
+    /// # Examples
+    ///
+    /// Set the lights of a group to a triadic harmony:
+    /// ```no_run
+    /// use huelib::{palette::{Harmony, Palette}, Color};
+    ///
+    /// # use huelib::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// let group = bridge.get_group("1")?;
+    /// let lights = group
+    ///     .lights
+    ///     .iter()
+    ///     .map(|id| bridge.get_light(id.as_str()))
+    ///     .collect::<huelib::Result<Vec<_>>>()?;
+    /// let base = Color::from_rgb(255, 80, 0);
+    /// let palette = Palette::from_harmony(base, Harmony::Triadic, lights.len());
+    /// for (id, state) in palette.light_states(&lights) {
+    ///     let modifier = huelib::resource::light::StateModifier::from(state);
+    ///     bridge.set_light_state(id, &modifier)?;
+    /// }
+    /// # Ok::<(), huelib::Error>(())
+    /// ```
+    pub fn from_harmony(base: Color, harmony: Harmony, count: usize) -> Self {
+        let (lightness, a, b) = rgb_to_oklab(base.to_rgb());
+        let chroma = (a * a + b * b).sqrt();
+        let hue = b.atan2(a);
+        let from_lch = |lightness: f64, hue: f64| {
+            oklab_to_rgb((lightness, chroma * hue.cos(), chroma * hue.sin()))
+        };
+        let spread = |index: usize, range: f64| match count {
+            0 | 1 => 0.0,
+            _ => index as f64 / (count - 1) as f64 * range,
+        };
+        let colors = (0..count).map(|index| match harmony {
+            Harmony::Complementary => {
+                from_lch(lightness, hue + (index % 2) as f64 * std::f64::consts::PI)
+            }
+            Harmony::Triadic => from_lch(
+                lightness,
+                hue + (index % 3) as f64 * 2.0 * std::f64::consts::FRAC_PI_3,
+            ),
+            Harmony::Analogous => {
+                let offset = match index % 2 {
+                    0 => spread(index, 1.0),
+                    _ => -spread(index, 1.0),
+                };
+                from_lch(lightness, hue + offset * std::f64::consts::FRAC_PI_6)
+            }
+            Harmony::Monochrome => {
+                let target = if lightness < 0.6 { 0.9 } else { 0.3 };
+                from_lch(lightness + spread(index, target - lightness), hue)
+            }
+        });
+        Self {
+            colors: colors.collect(),
+        }
+    }
+
+    /// Generates a palette with `count` colors that form a gradient between two colors.
+    ///
+    /// The colors are interpolated in the Oklab color space, so that the steps between them are
+    /// perceptually uniform. The first and the last color of the palette are the given colors.
+    pub fn from_gradient(start: Color, end: Color, count: usize) -> Self {
+        let (start, end) = (start.to_rgb(), end.to_rgb());
+        let colors = (0..count).map(|index| {
+            let t = match count {
+                1 => 0.0,
+                _ => index as f64 / (count - 1) as f64,
+            };
+            mix(start, end, t)
+        });
+        Self {
+            colors: colors.collect(),
+        }
+    }
+
+    /// Extracts a palette with at most `count` colors from a buffer of rgb values.
+    ///
+    /// Every three bytes of the buffer are one pixel, remaining bytes are ignored.
+    pub fn from_rgb_bytes(bytes: &[u8], count: usize) -> Self {
+        let pixels: Vec<(u8, u8, u8)> = bytes.chunks_exact(3).map(|v| (v[0], v[1], v[2])).collect();
+        Self::from_pixels(&pixels, count)
+    }
+
+    /// Returns the rgb values of the colors in the palette.
+    pub fn colors(&self) -> &[(u8, u8, u8)] {
+        &self.colors
+    }
+
+    /// Returns whether the palette contains no colors.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Returns the colors of the palette ordered by brightness and then by saturation.
+    pub fn ordered_colors(&self) -> Vec<(u8, u8, u8)> {
+        let mut colors = self.colors.clone();
+        colors.sort_by_key(|v| std::cmp::Reverse(value_and_saturation(*v)));
+        colors
+    }
+
+    /// Creates a scene that sets the given lights to the colors of the palette.
+    ///
+    /// The colors are ordered by brightness and then by saturation, and the lights keep the given
+    /// order: the brightest color is assigned to the first light, the second brightest color to
+    /// the second light, and so on. Pass the lights that should stand out first. If there are more
+    /// lights than colors, the colors are repeated. Every color is moved into the color gamut of
+    /// the light it is assigned to.
+    ///
+    /// With the `old-api` feature the capabilities of the lights are not available, so the colors
+    /// are not moved into the gamuts of the lights. The bridge then maps colors that are out of
+    /// gamut to the closest color a light can display.
+    pub fn scene_creator(&self, name: String, lights: &[Light]) -> scene::Creator {
+        let ids = lights.iter().map(|light| light.id.clone()).collect();
+        let creator = scene::Creator::new(name, ids);
+        let palette = Self {
+            colors: self.ordered_colors(),
+        };
+        match palette.is_empty() {
+            true => creator,
+            false => creator.with_light_states(palette.light_states(lights)),
+        }
+    }
+
+    /// Returns the states that set the given lights to the colors of the palette.
+    ///
+    /// The colors are assigned to the lights in the order of the palette and the lights keep the
+    /// given order. If there are more lights than colors, the colors are repeated. Every color is
+    /// moved into the color gamut of the light it is assigned to, except with the `old-api`
+    /// feature, see [`scene_creator`](Self::scene_creator).
+    ///
+    /// The keys of the HashMap are the light identifiers.
+    pub fn light_states(&self, lights: &[Light]) -> HashMap<String, light::StaticStateModifier> {
+        if self.colors.is_empty() {
+            return HashMap::new();
+        }
+        lights
+            .iter()
+            .zip(self.colors.iter().cycle())
+            .map(|(light, (red, green, blue))| {
+                let color = Color::from_rgb(*red, *green, *blue);
+                #[cfg(not(feature = "old-api"))]
+                let color =
+                    match crate::color::Gamut::from_capabilities(&light.capabilities.control) {
+                        Some(gamut) => color.clamp_to(&gamut),
+                        None => color,
+                    };
+                let mut state = light::StaticStateModifier::new()
+                    .with_on(true)
+                    .with_color_space_coordinates(color.space_coordinates);
+                if let Some(brightness) = color.brightness {
+                    state = state.with_brightness(Brightness::from_raw(brightness));
+                }
+                (light.id.clone(), state)
+            })
+            .collect()
+    }
+}
+
+/// Rule for generating harmonic colors from a base color.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Harmony {
+    /// Alternates between the base color and the color on the opposite side of the color wheel.
+    Complementary,
+    /// Rotates between three colors that are evenly spaced around the color wheel.
+    Triadic,
+    /// Spreads colors that are next to the base color on the color wheel.
+    Analogous,
+    /// Spreads colors with the hue of the base color and different lightness.
+    Monochrome,
+}
+
+/// Mixes two rgb values in the Oklab color space.
+///
+/// A factor of 0 returns the start and a factor of 1 the end.
+pub(crate) fn mix(start: (u8, u8, u8), end: (u8, u8, u8), factor: f64) -> (u8, u8, u8) {
+    let start = rgb_to_oklab(start);
+    let end = rgb_to_oklab(end);
+    oklab_to_rgb((
+        start.0 + (end.0 - start.0) * factor,
+        start.1 + (end.1 - start.1) * factor,
+        start.2 + (end.2 - start.2) * factor,
+    ))
+}
+
+/// Converts rgb values into the Oklab color space.
+fn rgb_to_oklab(rgb: (u8, u8, u8)) -> (f64, f64, f64) {
+    // NOTE: More information: https://bottosson.github.io/posts/oklab/
+    let linear = |v: u8| {
+        let v = v as f64 / 255.0;
+        if v > 0.04045 {
+            ((v + 0.055) / (1.0 + 0.055)).powf(2.4)
+        } else {
+            v / 12.92
+        }
+    };
+    let (red, green, blue) = (linear(rgb.0), linear(rgb.1), linear(rgb.2));
+    let l = (0.412_221_470_8 * red + 0.536_332_536_3 * green + 0.051_445_992_9 * blue).cbrt();
+    let m = (0.211_903_498_2 * red + 0.680_699_545_1 * green + 0.107_396_956_6 * blue).cbrt();
+    let s = (0.088_302_461_9 * red + 0.281_718_837_6 * green + 0.629_978_700_5 * blue).cbrt();
+    (
+        0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+        1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+        0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s,
+    )
+}
+
+/// Converts a color of the Oklab color space into rgb values.
+///
+/// Colors that are outside of the rgb color space are clamped.
+fn oklab_to_rgb(lab: (f64, f64, f64)) -> (u8, u8, u8) {
+    let (lightness, a, b) = lab;
+    let l = (lightness + 0.396_337_777_4 * a + 0.215_803_757_3 * b).powi(3);
+    let m = (lightness - 0.105_561_345_8 * a - 0.063_854_172_8 * b).powi(3);
+    let s = (lightness - 0.089_484_177_5 * a - 1.291_485_548_0 * b).powi(3);
+    let gamma_correct = |v: f64| {
+        let v = v.clamp(0.0, 1.0);
+        let v = if v <= 0.003_130_8 {
+            12.92 * v
+        } else {
+            (1.0 + 0.055) * v.powf(1.0 / 2.4) - 0.055
+        };
+        (v * 255.0).round() as u8
+    };
+    (
+        gamma_correct(4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s),
+        gamma_correct(-1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s),
+        gamma_correct(-0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s),
+    )
+}
+
+/// Returns the value and the saturation of the HSV color space scaled to 0 to 255.
+fn value_and_saturation(pixel: (u8, u8, u8)) -> (u8, u8) {
+    let max = pixel.0.max(pixel.1).max(pixel.2);
+    let min = pixel.0.min(pixel.1).min(pixel.2);
+    let saturation = match max {
+        0 => 0,
+        _ => ((max - min) as u16 * 255 / max as u16) as u8,
+    };
+    (max, saturation)
+}
+
+fn channel_value(pixel: (u8, u8, u8), channel: usize) -> u8 {
+    match channel {
+        0 => pixel.0,
+        1 => pixel.1,
+        _ => pixel.2,
+    }
+}
+
+fn widest_channel(pixels: &[(u8, u8, u8)]) -> (usize, u8) {
+    (0..3)
+        .map(|channel| {
+            let values = pixels.iter().map(|v| channel_value(*v, channel));
+            let min = values.clone().min().unwrap_or(0);
+            let max = values.max().unwrap_or(0);
+            (channel, max - min)
+        })
+        .max_by_key(|(_, range)| *range)
+        .unwrap_or((0, 0))
+}
+
+fn average(pixels: &[(u8, u8, u8)]) -> (u8, u8, u8) {
+    let len = pixels.len().max(1) as u64;
+    let sum = pixels.iter().fold((0u64, 0u64, 0u64), |sum, v| {
+        (sum.0 + v.0 as u64, sum.1 + v.1 as u64, sum.2 + v.2 as u64)
+    });
+    (
+        ((sum.0 + len / 2) / len) as u8,
+        ((sum.1 + len / 2) / len) as u8,
+        ((sum.2 + len / 2) / len) as u8,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::light;
+
+    #[test]
+    fn extract_palette() {
+        let mut pixels = vec![(250, 10, 10); 60];
+        pixels.extend(vec![(10, 10, 240); 30]);
+        pixels.extend(vec![(20, 200, 20); 10]);
+        let palette = Palette::from_pixels(&pixels, 3);
+        assert_eq!(
+            palette.colors(),
+            &[(250, 10, 10), (10, 10, 240), (20, 200, 20)]
+        );
+        assert_eq!(
+            palette.ordered_colors(),
+            vec![(250, 10, 10), (10, 10, 240), (20, 200, 20)]
+        );
+
+        let palette = Palette::from_pixels(&pixels, 10);
+        assert_eq!(palette.colors().len(), 3);
+        assert!(Palette::from_pixels(&[], 3).is_empty());
+        assert_eq!(
+            Palette::from_rgb_bytes(&[1, 2, 3, 1, 2, 3, 4], 2).colors(),
+            &[(1, 2, 3)]
+        );
+    }
+
+    #[test]
+    fn generate_palette() {
+        let near = |a: (u8, u8, u8), b: (u8, u8, u8)| {
+            (a.0 as i16 - b.0 as i16).abs() <= 1
+                && (a.1 as i16 - b.1 as i16).abs() <= 1
+                && (a.2 as i16 - b.2 as i16).abs() <= 1
+        };
+        for rgb in [(255, 0, 0), (12, 200, 90), (128, 128, 128)] {
+            assert!(near(oklab_to_rgb(rgb_to_oklab(rgb)), rgb));
+        }
+
+        let start = Color::from_rgb(255, 0, 0);
+        let end = Color::from_rgb(0, 0, 255);
+        let palette = Palette::from_gradient(start, end, 5);
+        assert_eq!(palette.colors().len(), 5);
+        assert!(near(palette.colors()[0], start.to_rgb()));
+        assert!(near(palette.colors()[4], end.to_rgb()));
+        assert_eq!(Palette::from_gradient(start, end, 1).colors().len(), 1);
+
+        let base = Color::from_rgb(255, 128, 0);
+        let palette = Palette::from_harmony(base, Harmony::Complementary, 4);
+        let colors = palette.colors();
+        assert_eq!(colors[0], colors[2]);
+        assert!(near(colors[0], base.to_rgb()));
+        let (hue, _, _) = Color::from_rgb(colors[1].0, colors[1].1, colors[1].2).to_hsv();
+        assert!(hue > 180.0 && hue < 240.0);
+
+        let palette = Palette::from_harmony(base, Harmony::Triadic, 6);
+        assert_eq!(palette.colors()[..3], palette.colors()[3..]);
+
+        let palette = Palette::from_harmony(base, Harmony::Monochrome, 3);
+        let values: Vec<_> = palette
+            .colors()
+            .iter()
+            .map(|v| value_and_saturation(*v).0)
+            .collect();
+        assert!(values[0] > values[1] && values[1] > values[2]);
+    }
+
+    #[test]
+    fn create_scene() {
+        let pixels = [(250, 10, 10), (250, 10, 10), (10, 240, 10)];
+        let palette = Palette::from_pixels(&pixels, 2);
+        let lights = [light("1", "B"), light("2", "C"), light("3", "B")];
+        let creator = palette.scene_creator("test".into(), &lights);
+        assert_eq!(creator.lights, vec!["1", "2", "3"]);
+        let light_states = creator.light_states.unwrap();
+        let xy = |id: &str| light_states[id].color_space_coordinates.unwrap();
+        assert_eq!(xy("1"), xy("3"));
+        assert_ne!(xy("1"), xy("2"));
+        // The brightest color, red, is assigned to the first light.
+        assert!(xy("1").0 > xy("2").0);
+        #[cfg(not(feature = "old-api"))]
+        assert!(
+            crate::color::Gamut::B.contains(xy("1")) && crate::color::Gamut::C.contains(xy("2"))
+        );
+        assert!(light_states.values().all(|v| v.on == Some(true)));
+
+        let palette =
+            Palette::from_gradient(Color::from_rgb(0, 255, 0), Color::from_rgb(255, 0, 0), 3);
+        let light_states = palette.light_states(&lights);
+        let xy = |id: &str| light_states[id].color_space_coordinates.unwrap();
+        assert!(xy("1").0 < xy("3").0);
+    }
+}
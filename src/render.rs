@@ -0,0 +1,397 @@
+use crate::resource::{
+    rule::{ActionRequestMethod, Condition, ConditionOperator},
+    schedule::{CommandRequestMethod, TimePattern, Weekdays},
+    Group, Light, Rule, Scene, Schedule, Sensor,
+};
+use crate::{Bridge, Result};
+use chrono::{Duration, Weekday};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+/// Renderer for rules and schedules in a human-readable form.
+///
+/// Addresses are resolved to the names of the lights, groups, sensors and scenes that were added
+/// to the renderer. Resources that are not known are rendered with their identifier.
+///
+/// # Examples
+///
+/// Print all rules of a bridge:
+/// ```no_run
+/// use huelib::render::Renderer;
+///
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// let renderer = Renderer::from_bridge(&bridge)?;
+/// for rule in bridge.get_all_rules()? {
+///     println!("{}: {}", rule.name, renderer.render_rule(&rule));
+/// }
+/// # Ok::<(), huelib::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Renderer {
+    lights: HashMap<String, String>,
+    groups: HashMap<String, String>,
+    sensors: HashMap<String, String>,
+    scenes: HashMap<String, String>,
+}
+
+impl Renderer {
+    /// Creates a new [`Renderer`] that does not know any resources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`Renderer`] that knows all lights, groups, sensors and scenes of a bridge.
+    pub fn from_bridge(bridge: &Bridge) -> Result<Self> {
+        Ok(Self::new()
+            .with_lights(&bridge.get_all_lights()?)
+            .with_groups(&bridge.get_all_groups()?)
+            .with_sensors(&bridge.get_all_sensors()?)
+            .with_scenes(&bridge.get_all_scenes()?))
+    }
+
+    /// Adds the names of the given lights.
+    pub fn with_lights(mut self, lights: &[Light]) -> Self {
+        self.lights
+            .extend(lights.iter().map(|v| (v.id.clone(), v.name.clone())));
+        self
+    }
+
+    /// Adds the names of the given groups.
+    pub fn with_groups(mut self, groups: &[Group]) -> Self {
+        self.groups
+            .extend(groups.iter().map(|v| (v.id.clone(), v.name.clone())));
+        self
+    }
+
+    /// Adds the names of the given sensors.
+    pub fn with_sensors(mut self, sensors: &[Sensor]) -> Self {
+        self.sensors
+            .extend(sensors.iter().map(|v| (v.id.clone(), v.name.clone())));
+        self
+    }
+
+    /// Adds the names of the given scenes.
+    pub fn with_scenes(mut self, scenes: &[Scene]) -> Self {
+        self.scenes
+            .extend(scenes.iter().map(|v| (v.id.clone(), v.name.clone())));
+        self
+    }
+
+    /// Renders a rule, for example `WHEN Hallway motion = true THEN turn on group 'Hallway'`.
+    pub fn render_rule(&self, rule: &Rule) -> String {
+        let conditions: Vec<String> = rule
+            .conditions
+            .iter()
+            .map(|v| self.render_condition(v))
+            .collect();
+        let actions: Vec<String> = rule
+            .actions
+            .iter()
+            .map(|v| {
+                let method = match v.request_method {
+                    ActionRequestMethod::Put => "PUT",
+                    ActionRequestMethod::Post => "POST",
+                    ActionRequestMethod::Delete => "DELETE",
+                };
+                self.render_request(&v.address, method, &v.body)
+            })
+            .collect();
+        format!(
+            "WHEN {} THEN {}",
+            conditions.join(" AND "),
+            actions.join(", ")
+        )
+    }
+
+    /// Renders a schedule, for example `AT 07:00:00 on Mon, Tue THEN turn on group 'Bedroom'`.
+    pub fn render_schedule(&self, schedule: &Schedule) -> String {
+        let command = &schedule.command;
+        let address = strip_api_prefix(&command.address);
+        let method = match command.request_method {
+            CommandRequestMethod::Put => "PUT",
+            CommandRequestMethod::Post => "POST",
+            CommandRequestMethod::Delete => "DELETE",
+        };
+        format!(
+            "AT {} THEN {}",
+            render_time_pattern(&schedule.local_time),
+            self.render_request(address, method, &command.body)
+        )
+    }
+
+    /// Renders a condition of a rule, for example `Hallway motion = true`.
+    pub fn render_condition(&self, condition: &Condition) -> String {
+        let value = condition.value.as_deref().unwrap_or_default();
+        if condition.address == "/config/localtime" {
+            let interval = match value.parse() {
+                Ok(TimePattern::Interval {
+                    weekdays,
+                    start,
+                    end,
+                }) => {
+                    let mut interval = format!("between {} and {}", start, end);
+                    if let Some(v) = weekdays {
+                        interval.push_str(&format!(" {}", render_weekdays(v)));
+                    }
+                    interval
+                }
+                _ => value.to_owned(),
+            };
+            return match condition.operator {
+                ConditionOperator::NotIn => format!("time not {}", interval),
+                _ => format!("time {}", interval),
+            };
+        }
+        let attribute = self.render_attribute(&condition.address);
+        let duration = || match value.parse() {
+            Ok(TimePattern::Timer { duration, .. }) => render_duration(duration),
+            _ => value.to_owned(),
+        };
+        match condition.operator {
+            ConditionOperator::Equals => format!("{} = {}", attribute, value),
+            ConditionOperator::LessThan => format!("{} < {}", attribute, value),
+            ConditionOperator::GreaterThan => format!("{} > {}", attribute, value),
+            ConditionOperator::Dx => format!("{} changes", attribute),
+            ConditionOperator::Ddx => {
+                format!("{} changed {} ago", attribute, duration())
+            }
+            ConditionOperator::Stable => {
+                format!("{} unchanged for {}", attribute, duration())
+            }
+            ConditionOperator::NotStable => {
+                format!("{} changed within {}", attribute, duration())
+            }
+            ConditionOperator::In => format!("{} in {}", attribute, value),
+            ConditionOperator::NotIn => format!("{} not in {}", attribute, value),
+        }
+    }
+
+    fn render_attribute(&self, address: &str) -> String {
+        let parts: Vec<&str> = address.trim_start_matches('/').split('/').collect();
+        match parts.as_slice() {
+            ["sensors", id, _, attribute] => format!(
+                "{} {}",
+                self.sensors
+                    .get(*id)
+                    .cloned()
+                    .unwrap_or_else(|| format!("sensor {}", id)),
+                attribute_name(attribute)
+            ),
+            ["groups", id, _, attribute] => format!(
+                "{} {}",
+                self.name(&self.groups, "group", id),
+                attribute_name(attribute)
+            ),
+            _ => address.to_owned(),
+        }
+    }
+
+    fn render_request(&self, address: &str, method: &str, body: &JsonValue) -> String {
+        let parts: Vec<&str> = address.trim_start_matches('/').split('/').collect();
+        let (kind, id) = match parts.as_slice() {
+            ["lights", id, ..] => ("light", *id),
+            ["groups", id, ..] => ("group", *id),
+            ["sensors", id, ..] => ("sensor", *id),
+            ["scenes", id, ..] => ("scene", *id),
+            ["rules", id, ..] => ("rule", *id),
+            ["schedules", id, ..] => ("schedule", *id),
+            ["resourcelinks", id, ..] => ("resourcelink", *id),
+            _ => return format!("{} {} {}", method, address, body),
+        };
+        let target = match kind {
+            "light" => self.name(&self.lights, kind, id),
+            "group" => self.name(&self.groups, kind, id),
+            "sensor" => self.name(&self.sensors, kind, id),
+            "scene" => self.name(&self.scenes, kind, id),
+            _ => format!("{} {}", kind, id),
+        };
+        if method == "DELETE" {
+            return format!("delete {}", target);
+        }
+        let map = match (method, parts.len(), body.as_object()) {
+            ("PUT", 3, Some(v)) => v,
+            _ => return format!("{} {} {}", method, address, body),
+        };
+        let mut parts = Vec::new();
+        let mut settings = Vec::new();
+        for (key, value) in map {
+            match (key.as_str(), value) {
+                ("scene", JsonValue::String(v)) => parts.push(format!(
+                    "recall {} in {}",
+                    self.name(&self.scenes, "scene", v),
+                    target
+                )),
+                ("on", JsonValue::Bool(true)) => parts.push(format!("turn on {}", target)),
+                ("on", JsonValue::Bool(false)) => parts.push(format!("turn off {}", target)),
+                (key, value) => settings.push(format!("{} = {}", attribute_name(key), value)),
+            }
+        }
+        if !settings.is_empty() {
+            parts.push(format!("set {} of {}", settings.join(", "), target));
+        }
+        parts.join(", ")
+    }
+
+    fn name(&self, names: &HashMap<String, String>, kind: &str, id: &str) -> String {
+        match names.get(id) {
+            Some(v) => format!("{} '{}'", kind, v),
+            None => format!("{} {}", kind, id),
+        }
+    }
+}
+
+/// Removes the `/api/<username>` prefix of a schedule command address.
+fn strip_api_prefix(address: &str) -> &str {
+    match address.strip_prefix("/api/") {
+        Some(v) => v.find('/').map_or(address, |i| &v[i..]),
+        None => address,
+    }
+}
+
+fn attribute_name(attribute: &str) -> String {
+    match attribute {
+        "presence" => "motion".to_owned(),
+        "buttonevent" => "button event".to_owned(),
+        "lightlevel" => "light level".to_owned(),
+        "lastupdated" => "last updated".to_owned(),
+        "bri" => "brightness".to_owned(),
+        "bri_inc" => "brightness change".to_owned(),
+        "ct" => "color temperature".to_owned(),
+        "sat" => "saturation".to_owned(),
+        "transitiontime" => "transition time".to_owned(),
+        v => v.replace('_', " "),
+    }
+}
+
+fn render_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds();
+    let mut parts = Vec::new();
+    if seconds >= 3600 {
+        parts.push(format!("{}h", seconds / 3600));
+    }
+    if seconds % 3600 >= 60 {
+        parts.push(format!("{}m", seconds / 60 % 60));
+    }
+    if seconds % 60 > 0 || seconds == 0 {
+        parts.push(format!("{}s", seconds % 60));
+    }
+    parts.join(" ")
+}
+
+fn render_weekdays(weekdays: Weekdays) -> String {
+    if weekdays == Weekdays::ALL {
+        return "every day".to_owned();
+    }
+    let names: Vec<String> = [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ]
+    .iter()
+    .filter(|v| weekdays.contains(**v))
+    .map(|v| v.to_string())
+    .collect();
+    format!("on {}", names.join(", "))
+}
+
+fn render_time_pattern(pattern: &TimePattern) -> String {
+    let (text, random) = match pattern {
+        TimePattern::Absolute { time, random } => (time.to_string(), random),
+        TimePattern::Recurring {
+            weekdays,
+            time,
+            random,
+        } => (format!("{} {}", time, render_weekdays(*weekdays)), random),
+        TimePattern::Timer { duration, random } => {
+            (format!("in {}", render_duration(*duration)), random)
+        }
+        TimePattern::RecurringTimer {
+            count,
+            duration,
+            random,
+        } => {
+            let mut text = format!("every {}", render_duration(*duration));
+            if let Some(v) = count {
+                text.push_str(&format!(" ({} times)", v));
+            }
+            (text, random)
+        }
+        TimePattern::Interval { .. } | TimePattern::Unknown(_) => return pattern.to_string(),
+    };
+    match random {
+        Some(v) => format!("{} (randomized by up to {})", text, render_duration(*v)),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn renderer() -> Renderer {
+        let mut renderer = Renderer::new();
+        renderer.sensors.insert("5".into(), "Hallway".into());
+        renderer.sensors.insert("1".into(), "Daylight".into());
+        renderer.groups.insert("4".into(), "Hallway".into());
+        renderer.scenes.insert("abc".into(), "Evening".into());
+        renderer
+    }
+
+    #[test]
+    fn render_rule() {
+        let rule: Rule = serde_json::from_value(json!({
+            "name": "Hallway on",
+            "owner": "user",
+            "lasttriggered": "none",
+            "timestriggered": 0,
+            "created": "2020-01-01T00:00:00",
+            "status": "enabled",
+            "conditions": [
+                {"address": "/sensors/5/state/presence", "operator": "eq", "value": "true"},
+                {"address": "/sensors/1/state/dark", "operator": "eq", "value": "true"},
+                {"address": "/sensors/5/state/presence", "operator": "ddx", "value": "PT00:05:00"},
+                {"address": "/config/localtime", "operator": "in", "value": "T20:00:00/T08:00:00"}
+            ],
+            "actions": [
+                {"address": "/groups/4/action", "method": "PUT", "body": {"scene": "abc"}},
+                {"address": "/lights/2/state", "method": "PUT", "body": {"on": false, "bri": 10}},
+                {"address": "/scenes/xyz", "method": "DELETE", "body": {}}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            renderer().render_rule(&rule),
+            "WHEN Hallway motion = true AND Daylight dark = true AND Hallway motion changed 5m \
+             ago AND time between 20:00:00 and 08:00:00 THEN recall scene 'Evening' in group \
+             'Hallway', turn off light 2, set brightness = 10 of light 2, delete scene xyz"
+        );
+    }
+
+    #[test]
+    fn render_schedule() {
+        let schedule: Schedule = serde_json::from_value(json!({
+            "name": "Wake up",
+            "description": "",
+            "command": {
+                "address": "/api/user/groups/4/action",
+                "method": "PUT",
+                "body": {"on": true}
+            },
+            "localtime": "W124/T07:00:00A00:10:00",
+            "status": "enabled"
+        }))
+        .unwrap();
+        assert_eq!(
+            renderer().render_schedule(&schedule),
+            "AT 07:00:00 on Mon, Tue, Wed, Thu, Fri (randomized by up to 10m) THEN turn on group \
+             'Hallway'"
+        );
+    }
+}
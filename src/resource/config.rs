@@ -1,5 +1,5 @@
 use crate::{resource, util};
-use chrono::{NaiveDateTime, NaiveTime};
+use chrono::{FixedOffset, NaiveDateTime, NaiveTime};
 use derive_setters::Setters;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_repr::Deserialize_repr;
@@ -90,6 +90,24 @@ pub struct Config {
     pub whitelist: Vec<User>,
 }
 
+impl Config {
+    /// Returns the offset of the local time of the bridge from UTC.
+    ///
+    /// The offset is derived from the local and UTC time of the bridge and rounded to full
+    /// minutes. If the local time is not set, the offset is zero.
+    ///
+    /// This is the current offset of the bridge. The [`timezone`](Self::timezone) is not
+    /// resolved, so changes of the offset because of daylight saving time are not included.
+    pub fn utc_offset(&self) -> FixedOffset {
+        let seconds = match self.local_time {
+            Some(v) => (v - self.current_time).num_seconds(),
+            None => 0,
+        };
+        let minutes = (seconds as f64 / 60.0).round() as i32;
+        FixedOffset::east_opt(minutes * 60).unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+    }
+}
+
 impl resource::Resource for Config {}
 
 fn deserialize_whitelist<'de, D: Deserializer<'de>>(
@@ -352,8 +370,8 @@ mod tests {
             touchlink: Some(false),
             zigbee_channel: Some(1),
             current_time: Some(NaiveDateTime::new(
-                NaiveDate::from_ymd(2020, 1, 1),
-                NaiveTime::from_hms(0, 0, 0),
+                NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
             )),
             timezone: Some("Europe/Berlin".into()),
         };
@@ -1,10 +1,12 @@
-use crate::resource::{self, Adjust, Alert, Effect};
+use crate::resource::{self, light, Adjust, Alert, Effect, TransitionTime};
 use crate::Color;
 use derive_setters::Setters;
-use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
 /// A group of lights.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
 pub struct Group {
     /// Identifier of the group.
     #[serde(skip)]
@@ -40,6 +42,10 @@ pub struct Group {
     pub unique_id: Option<String>,
     /// Whether the group is automatically deleted when not referenced anymore.
     pub recycle: Option<bool>,
+    /// Locations of the lights in the group.
+    ///
+    /// The keys of the HashMap are the light identifiers. Only present for entertainment groups.
+    pub locations: Option<HashMap<String, Location>>,
 }
 
 impl Group {
@@ -48,6 +54,94 @@ impl Group {
     }
 }
 
+impl Hash for Group {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.name.hash(state);
+        self.lights.hash(state);
+        self.sensors.hash(state);
+        self.kind.hash(state);
+        self.class.hash(state);
+        self.state.hash(state);
+        self.model_id.hash(state);
+        self.unique_id.hash(state);
+        self.recycle.hash(state);
+        // A HashMap has no fixed order, so the locations are hashed sorted by their keys.
+        let mut locations: Vec<_> = self.locations.iter().flatten().collect();
+        locations.sort_by(|a, b| a.0.cmp(b.0));
+        locations.hash(state);
+    }
+}
+
+/// Location of a light in an entertainment group.
+///
+/// All coordinates are between -1 and 1. The x axis goes from left to right, the y axis from the
+/// back to the front and the z axis from the floor to the ceiling.
+///
+/// Locations are compared and hashed by the bits of their coordinates, so that they can be used
+/// in hash maps and sets.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Location {
+    /// Position on the x axis.
+    pub x: f32,
+    /// Position on the y axis.
+    pub y: f32,
+    /// Position on the z axis.
+    pub z: f32,
+}
+
+impl Location {
+    /// Creates a new location.
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the distance to another location.
+    pub fn distance(&self, other: &Location) -> f32 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2))
+            .sqrt()
+    }
+
+    fn to_bits(self) -> (u32, u32, u32) {
+        (self.x.to_bits(), self.y.to_bits(), self.z.to_bits())
+    }
+}
+
+impl PartialEq for Location {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl Eq for Location {}
+
+impl Hash for Location {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bits().hash(state);
+    }
+}
+
+impl Serialize for Location {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [self.x, self.y, self.z].serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Location {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Older versions of the API only return the x and y coordinates.
+        let value: Vec<f32> = Deserialize::deserialize(deserializer)?;
+        match value.as_slice() {
+            [x, y] => Ok(Self::new(*x, *y, 0.0)),
+            [x, y, z] => Ok(Self::new(*x, *y, *z)),
+            _ => Err(serde::de::Error::invalid_length(
+                value.len(),
+                &"2 or 3 coordinates",
+            )),
+        }
+    }
+}
+
 impl resource::Resource for Group {}
 
 /// Kind of a group.
@@ -170,7 +264,7 @@ pub struct State {
 }
 
 /// Struct for creating a group.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Creator {
     /// Sets the name of the group.
@@ -214,7 +308,7 @@ impl resource::Creator for Creator {
 }
 
 /// Struct for modifying group attributes.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct AttributeModifier {
     /// Sets the name of the group.
@@ -252,6 +346,12 @@ pub struct StateModifier {
     /// Turns the lights on or off.
     pub on: Option<bool>,
     /// Sets the brightness of the lights.
+    ///
+    /// The [`with_brightness`] setter also takes a [`Brightness`].
+    ///
+    /// [`with_brightness`]: Self::with_brightness
+    /// [`Brightness`]: resource::Brightness
+    #[setters(skip)]
     pub brightness: Option<Adjust<u8>>,
     /// Sets the hue of the lights.
     pub hue: Option<Adjust<u16>>,
@@ -260,6 +360,12 @@ pub struct StateModifier {
     /// Sets the color space coordinates of the lights.
     pub color_space_coordinates: Option<Adjust<(f32, f32)>>,
     /// Sets the color temperature of the lights.
+    ///
+    /// The [`with_color_temperature`] setter also takes a [`ColorTemperature`].
+    ///
+    /// [`with_color_temperature`]: Self::with_color_temperature
+    /// [`ColorTemperature`]: crate::color::ColorTemperature
+    #[setters(skip)]
     pub color_temperature: Option<Adjust<u16>>,
     /// Sets the alert effect of the lights.
     pub alert: Option<Alert>,
@@ -267,8 +373,8 @@ pub struct StateModifier {
     pub effect: Option<Effect>,
     /// Sets the transition duration of state changes.
     ///
-    /// This is given as a multiple of 100ms.
-    pub transition_time: Option<u16>,
+    /// Use [`TransitionTime::from_duration`] to create it from a duration.
+    pub transition_time: Option<TransitionTime>,
     /// Sets the scene identifier of the group.
     pub scene: Option<String>,
 }
@@ -279,6 +385,22 @@ impl StateModifier {
         Self::default()
     }
 
+    /// Sets the brightness of the lights.
+    pub fn with_brightness(self, value: impl Into<Adjust<u8>>) -> Self {
+        Self {
+            brightness: Some(value.into()),
+            ..self
+        }
+    }
+
+    /// Sets the color temperature of the lights.
+    pub fn with_color_temperature(self, value: impl Into<Adjust<u16>>) -> Self {
+        Self {
+            color_temperature: Some(value.into()),
+            ..self
+        }
+    }
+
     /// Convenient method to set the [`color_space_coordinates`] and [`brightness`] fields.
     ///
     /// [`color_space_coordinates`]: Self::color_space_coordinates
@@ -328,11 +450,52 @@ impl Serialize for StateModifier {
     }
 }
 
+impl<'de> Deserialize<'de> for StateModifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut raw = light::RawStateModifier::deserialize(deserializer)?;
+        let scene = raw.scene.take();
+        let modifier = light::StateModifier::from(raw);
+        Ok(Self {
+            on: modifier.on,
+            brightness: modifier.brightness,
+            hue: modifier.hue,
+            saturation: modifier.saturation,
+            color_space_coordinates: modifier.color_space_coordinates,
+            color_temperature: modifier.color_temperature,
+            alert: modifier.alert,
+            effect: modifier.effect,
+            transition_time: modifier.transition_time,
+            scene,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn deserialize_locations() {
+        let json = json!({
+            "name": "Entertainment area",
+            "lights": ["1", "2"],
+            "sensors": [],
+            "type": "Entertainment",
+            "locations": {"1": [-0.5, 1.0, 0.25], "2": [0.5, -1.0]}
+        });
+        let group: Group = serde_json::from_value(json).unwrap();
+        let locations = group.locations.as_ref().unwrap();
+        assert_eq!(locations["1"], Location::new(-0.5, 1.0, 0.25));
+        assert_eq!(locations["2"], Location::new(0.5, -1.0, 0.0));
+        let location_json = serde_json::to_value(locations["1"]).unwrap();
+        assert_eq!(location_json, json!([-0.5, 1.0, 0.25]));
+        let location: Location = serde_json::from_value(location_json).unwrap();
+        assert_eq!(location, locations["1"]);
+        let groups: std::collections::HashSet<_> = vec![group.clone(), group].into_iter().collect();
+        assert_eq!(groups.len(), 1);
+    }
+
     #[test]
     fn serialize_creator() {
         let creator = Creator::new("test".into(), vec!["1".into(), "2".into()]);
@@ -402,7 +565,7 @@ mod tests {
             color_temperature: Some(Adjust::Override(4)),
             alert: Some(Alert::None),
             effect: Some(Effect::Colorloop),
-            transition_time: Some(4),
+            transition_time: Some(TransitionTime::from_raw(4)),
             scene: Some("1".into()),
         };
         let modifier_json = serde_json::to_value(modifier).unwrap();
@@ -1,9 +1,11 @@
 #![allow(clippy::needless_update)]
 
-use crate::resource::{self, Adjust, Alert, ColorMode, Effect};
+use crate::color::ColorTemperature;
+use crate::resource::{self, Adjust, Alert, ColorMode, Effect, TransitionTime};
+use crate::util;
 use crate::Color;
 use derive_setters::Setters;
-use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 
 /// A light.
 #[derive(Clone, Debug, PartialEq, Deserialize)]
@@ -195,7 +197,7 @@ pub struct StreamingCapabilities {
 }
 
 /// Modifier for light attributes.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct AttributeModifier {
     /// Sets the name of the light.
@@ -226,14 +228,20 @@ impl resource::Modifier for AttributeModifier {
 ///
 /// [`scene::Modifier`]: super::scene::Modifier
 /// [`scene::Creator`]: super::scene::Creator
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct StaticStateModifier {
     /// Turns the light on or off.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on: Option<bool>,
     /// Sets the brightness of the light.
+    ///
+    /// The [`with_brightness`] setter also takes a [`Brightness`].
+    ///
+    /// [`with_brightness`]: Self::with_brightness
+    /// [`Brightness`]: resource::Brightness
     #[serde(skip_serializing_if = "Option::is_none", rename = "bri")]
+    #[setters(skip)]
     pub brightness: Option<u8>,
     /// Sets the hue of the light.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -244,17 +252,23 @@ pub struct StaticStateModifier {
     /// Sets the color space coordinates of the light.
     #[serde(skip_serializing_if = "Option::is_none", rename = "xy")]
     pub color_space_coordinates: Option<(f32, f32)>,
-    /// Sets the color temperature of a light.
+    /// Sets the color temperature of a light in mired.
+    ///
+    /// The [`with_color_temperature`] setter also takes a [`ColorTemperature`].
+    ///
+    /// [`with_color_temperature`]: Self::with_color_temperature
+    /// [`ColorTemperature`]: crate::color::ColorTemperature
     #[serde(skip_serializing_if = "Option::is_none", rename = "ct")]
+    #[setters(skip)]
     pub color_temperature: Option<u16>,
     /// Sets the dynamic effect of a light.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub effect: Option<Effect>,
     /// Sets the transition duration of state changes.
     ///
-    /// This is given as a multiple of 100ms.
+    /// Use [`TransitionTime::from_duration`] to create it from a duration.
     #[serde(skip_serializing_if = "Option::is_none", rename = "transitiontime")]
-    pub transition_time: Option<u16>,
+    pub transition_time: Option<TransitionTime>,
 }
 
 impl StaticStateModifier {
@@ -277,6 +291,22 @@ impl StaticStateModifier {
         }
         modifier
     }
+
+    /// Sets the brightness of a light.
+    pub fn with_brightness(self, value: impl Into<u8>) -> Self {
+        Self {
+            brightness: Some(value.into()),
+            ..self
+        }
+    }
+
+    /// Sets the color temperature of a light.
+    pub fn with_color_temperature(self, value: impl Into<ColorTemperature>) -> Self {
+        Self {
+            color_temperature: Some(value.into().mired()),
+            ..self
+        }
+    }
 }
 
 impl resource::Modifier for StaticStateModifier {
@@ -293,6 +323,12 @@ pub struct StateModifier {
     /// Turns the light on or off.
     pub on: Option<bool>,
     /// Sets the brightness of the light.
+    ///
+    /// The [`with_brightness`] setter also takes a [`Brightness`].
+    ///
+    /// [`with_brightness`]: Self::with_brightness
+    /// [`Brightness`]: resource::Brightness
+    #[setters(skip)]
     pub brightness: Option<Adjust<u8>>,
     /// Sets the hue of a light.
     pub hue: Option<Adjust<u16>>,
@@ -301,6 +337,12 @@ pub struct StateModifier {
     /// Sets the color space coordinates of the light.
     pub color_space_coordinates: Option<Adjust<(f32, f32)>>,
     /// Sets the color temperature of a light.
+    ///
+    /// The [`with_color_temperature`] setter also takes a [`ColorTemperature`].
+    ///
+    /// [`with_color_temperature`]: Self::with_color_temperature
+    /// [`ColorTemperature`]: crate::color::ColorTemperature
+    #[setters(skip)]
     pub color_temperature: Option<Adjust<u16>>,
     /// Sets the alert effect of a light.
     pub alert: Option<Alert>,
@@ -308,8 +350,8 @@ pub struct StateModifier {
     pub effect: Option<Effect>,
     /// Sets the transition duration of state changes.
     ///
-    /// This is given as a multiple of 100ms.
-    pub transition_time: Option<u16>,
+    /// Use [`TransitionTime::from_duration`] to create it from a duration.
+    pub transition_time: Option<TransitionTime>,
 }
 
 impl StateModifier {
@@ -318,6 +360,22 @@ impl StateModifier {
         Self::default()
     }
 
+    /// Sets the brightness of a light.
+    pub fn with_brightness(self, value: impl Into<Adjust<u8>>) -> Self {
+        Self {
+            brightness: Some(value.into()),
+            ..self
+        }
+    }
+
+    /// Sets the color temperature of a light.
+    pub fn with_color_temperature(self, value: impl Into<Adjust<u16>>) -> Self {
+        Self {
+            color_temperature: Some(value.into()),
+            ..self
+        }
+    }
+
     /// Convenient method to set the [`color_space_coordinates`] and [`brightness`] fields.
     ///
     /// [`color_space_coordinates`]: Self::color_space_coordinates
@@ -332,6 +390,27 @@ impl StateModifier {
         }
         modifier
     }
+
+    /// Convenient method to set the [`color_space_coordinates`] and [`brightness`] fields for a
+    /// specific light.
+    ///
+    /// The color is moved into the color gamut of the light, so that the same color looks
+    /// consistent across different lights. If the gamut of the light is unknown, this is the same
+    /// as [`with_color`]. With the `old-api` feature the color is never moved.
+    ///
+    /// [`color_space_coordinates`]: Self::color_space_coordinates
+    /// [`brightness`]: Self::brightness
+    /// [`with_color`]: Self::with_color
+    pub fn with_color_for(self, light: &Light, value: Color) -> Self {
+        #[cfg(not(feature = "old-api"))]
+        let value = match crate::color::Gamut::from_capabilities(&light.capabilities.control) {
+            Some(gamut) => value.clamp_to(&gamut),
+            None => value,
+        };
+        #[cfg(feature = "old-api")]
+        let _ = light;
+        self.with_color(value)
+    }
 }
 
 impl resource::Modifier for StateModifier {
@@ -366,8 +445,66 @@ impl Serialize for StateModifier {
     }
 }
 
+impl<'de> Deserialize<'de> for StateModifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(RawStateModifier::deserialize(deserializer)?.into())
+    }
+}
+
+/// Attributes of a light or group state modifier as they are sent to the Philips Hue API.
+#[derive(Deserialize)]
+pub(crate) struct RawStateModifier {
+    on: Option<bool>,
+    bri: Option<u8>,
+    bri_inc: Option<i16>,
+    hue: Option<u16>,
+    hue_inc: Option<i32>,
+    sat: Option<u8>,
+    sat_inc: Option<i16>,
+    xy: Option<(f32, f32)>,
+    xy_inc: Option<(f32, f32)>,
+    ct: Option<u16>,
+    ct_inc: Option<i32>,
+    alert: Option<Alert>,
+    effect: Option<Effect>,
+    transitiontime: Option<TransitionTime>,
+    pub(crate) scene: Option<String>,
+}
+
+impl From<StaticStateModifier> for StateModifier {
+    fn from(modifier: StaticStateModifier) -> Self {
+        Self {
+            on: modifier.on,
+            brightness: modifier.brightness.map(Adjust::Override),
+            hue: modifier.hue.map(Adjust::Override),
+            saturation: modifier.saturation.map(Adjust::Override),
+            color_space_coordinates: modifier.color_space_coordinates.map(Adjust::Override),
+            color_temperature: modifier.color_temperature.map(Adjust::Override),
+            alert: None,
+            effect: modifier.effect,
+            transition_time: modifier.transition_time,
+        }
+    }
+}
+
+impl From<RawStateModifier> for StateModifier {
+    fn from(raw: RawStateModifier) -> Self {
+        Self {
+            on: raw.on,
+            brightness: util::to_adjust(raw.bri, raw.bri_inc),
+            hue: util::to_adjust(raw.hue, raw.hue_inc),
+            saturation: util::to_adjust(raw.sat, raw.sat_inc),
+            color_space_coordinates: util::to_adjust_tuple(raw.xy, raw.xy_inc),
+            color_temperature: util::to_adjust(raw.ct, raw.ct_inc),
+            alert: raw.alert,
+            effect: raw.effect,
+            transition_time: raw.transitiontime,
+        }
+    }
+}
+
 /// Scanner for new lights.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Scanner {
     /// The device identifiers.
@@ -423,7 +560,7 @@ mod tests {
             color_space_coordinates: None,
             color_temperature: Some(4),
             effect: Some(Effect::Colorloop),
-            transition_time: Some(4),
+            transition_time: Some(TransitionTime::from_raw(4)),
         };
         let modifier_json = serde_json::to_value(modifier).unwrap();
         let expected_json = json!({
@@ -446,6 +583,15 @@ mod tests {
             "xy": [0.0, 0.0]
         });
         assert_eq!(modifier_json, expected_json);
+
+        let modifier = StaticStateModifier::new().with_color_temperature(300);
+        assert_eq!(
+            modifier,
+            StaticStateModifier::new().with_color_temperature(ColorTemperature::from_kelvin(3333))
+        );
+
+        let modifier = StaticStateModifier::new().with_brightness(resource::Brightness::MAX);
+        assert_eq!(modifier, StaticStateModifier::new().with_brightness(254));
     }
 
     #[test]
@@ -464,7 +610,7 @@ mod tests {
             color_temperature: Some(Adjust::Override(4)),
             alert: Some(Alert::None),
             effect: Some(Effect::Colorloop),
-            transition_time: Some(4),
+            transition_time: Some(TransitionTime::from_raw(4)),
         };
         let modifier_json = serde_json::to_value(modifier).unwrap();
         let expected_json = json!({
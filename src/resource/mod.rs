@@ -47,9 +47,10 @@ pub use sensor::Sensor;
 
 use crate::{response::Modified, Bridge, Error, Response};
 use chrono::NaiveDateTime;
-use serde::{de, de::Error as _, Deserialize, Serialize};
+use serde::{de, de::DeserializeOwned, de::Error as _, Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::fmt;
+use std::{collections::BTreeSet, convert::TryFrom, fmt, time::Duration};
+use thiserror::Error as ThisError;
 
 /// Alert effect of a light.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
@@ -202,6 +203,181 @@ pub enum Adjust<T> {
     Decrement(T),
 }
 
+/// Brightness of a light.
+///
+/// The Philips Hue API uses raw values between 1 (minimum) and 254 (maximum). The brightness can
+/// also be given as a percentage of the light output or as a perceptual percentage, which is gamma
+/// corrected so that 50% looks half as bright as 100%.
+///
+/// # Examples
+///
+/// Set a light to half of the perceived brightness:
+/// ```
+/// use huelib::resource::{light, Brightness};
+///
+/// let modifier = light::StateModifier::new().with_brightness(Brightness::from_perceptual(50.0));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Brightness(u8);
+
+impl Brightness {
+    /// The minimum brightness.
+    pub const MIN: Self = Self(1);
+    /// The maximum brightness.
+    pub const MAX: Self = Self(254);
+
+    const GAMMA: f32 = 2.2;
+
+    /// Creates a new brightness from a raw value.
+    ///
+    /// The value is clamped between 1 and 254.
+    pub fn from_raw(value: u8) -> Self {
+        Self(value.clamp(Self::MIN.0, Self::MAX.0))
+    }
+
+    /// Creates a new brightness from a percentage of the light output.
+    pub fn from_percent(value: f32) -> Self {
+        Self::from_output(value / 100.0, 0.0)
+    }
+
+    /// Creates a new brightness from a perceptual percentage.
+    pub fn from_perceptual(value: f32) -> Self {
+        Self::from_output((value / 100.0).clamp(0.0, 1.0).powf(Self::GAMMA), 0.0)
+    }
+
+    /// Creates a new brightness from a percentage of the light output of a specific light.
+    ///
+    /// The minimum dim level of the light is taken into account, so that the same percentage
+    /// results in the same light output on every light.
+    pub fn from_percent_for(value: f32, capabilities: &light::ControlCapabilities) -> Self {
+        Self::from_output(value / 100.0, Self::min_output(capabilities))
+    }
+
+    /// Creates a new brightness from a perceptual percentage for a specific light.
+    ///
+    /// The minimum dim level of the light is taken into account, so that the same percentage
+    /// looks the same on every light.
+    pub fn from_perceptual_for(value: f32, capabilities: &light::ControlCapabilities) -> Self {
+        let output = (value / 100.0).clamp(0.0, 1.0).powf(Self::GAMMA);
+        Self::from_output(output, Self::min_output(capabilities))
+    }
+
+    /// Returns the raw value of the brightness.
+    pub fn raw(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns the brightness as a percentage of the light output.
+    pub fn percent(&self) -> f32 {
+        (self.0 - Self::MIN.0) as f32 / (Self::MAX.0 - Self::MIN.0) as f32 * 100.0
+    }
+
+    /// Returns the brightness as a perceptual percentage.
+    pub fn perceptual(&self) -> f32 {
+        (self.percent() / 100.0).powf(1.0 / Self::GAMMA) * 100.0
+    }
+
+    /// Returns the light output at the minimum brightness as a fraction of the maximum output.
+    ///
+    /// The minimum dim level is given in hundredths of a percent.
+    fn min_output(capabilities: &light::ControlCapabilities) -> f32 {
+        let min_dimlevel = capabilities.min_dimlevel.unwrap_or(0) as f32;
+        (min_dimlevel / 10000.0).clamp(0.0, 1.0)
+    }
+
+    fn from_output(output: f32, min_output: f32) -> Self {
+        if min_output >= 1.0 {
+            return Self::MAX;
+        }
+        let output = ((output - min_output) / (1.0 - min_output)).clamp(0.0, 1.0);
+        let range = (Self::MAX.0 - Self::MIN.0) as f32;
+        Self(Self::MIN.0 + (output * range).round() as u8)
+    }
+}
+
+impl From<Brightness> for u8 {
+    fn from(value: Brightness) -> Self {
+        value.0
+    }
+}
+
+impl From<Brightness> for Adjust<u8> {
+    fn from(value: Brightness) -> Self {
+        Adjust::Override(value.0)
+    }
+}
+
+/// Duration of the transition between two states of a light.
+///
+/// The Philips Hue API uses multiples of 100ms with a maximum of 65535, which is about 1.8 hours.
+///
+/// # Examples
+///
+/// Set a transition time of two seconds:
+/// ```
+/// use huelib::resource::{light, TransitionTime};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), huelib::resource::TransitionTimeError> {
+/// let transition_time = TransitionTime::from_duration(Duration::from_secs(2))?;
+/// let modifier = light::StateModifier::new().with_transition_time(transition_time);
+/// // The same transition time given as a multiple of 100ms
+/// let modifier = light::StateModifier::new().with_transition_time(TransitionTime::from_raw(20));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize,
+)]
+#[serde(transparent)]
+pub struct TransitionTime(u16);
+
+impl TransitionTime {
+    /// The maximum transition time.
+    pub const MAX: Self = Self(u16::MAX);
+
+    /// Creates a new transition time from a multiple of 100ms.
+    pub fn from_raw(value: u16) -> Self {
+        Self(value)
+    }
+
+    /// Creates a new transition time from a duration.
+    ///
+    /// The duration is rounded to the nearest multiple of 100ms. Returns an error if the duration
+    /// is longer than the maximum transition time.
+    pub fn from_duration(value: Duration) -> Result<Self, TransitionTimeError> {
+        let raw = (value.as_millis() + 50) / 100;
+        match raw > u16::MAX as u128 {
+            true => Err(TransitionTimeError(value)),
+            false => Ok(Self(raw as u16)),
+        }
+    }
+
+    /// Returns the transition time as a multiple of 100ms.
+    pub fn raw(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns the transition time as a duration.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.0 as u64 * 100)
+    }
+}
+
+impl TryFrom<Duration> for TransitionTime {
+    type Error = TransitionTimeError;
+
+    /// See [`TransitionTime::from_duration`].
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        Self::from_duration(value)
+    }
+}
+
+/// Error that occurs when a duration is longer than the maximum transition time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ThisError)]
+#[error("Duration of {0:?} is longer than the maximum transition time")]
+pub struct TransitionTimeError(pub Duration);
+
 /// Represents a HTTP method.
 #[allow(missing_docs)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -212,6 +388,195 @@ pub enum RequestMethod {
     Delete,
 }
 
+/// Typed operation that is sent by a rule action or a schedule command.
+///
+/// States of lights are decoded into a [`light::StateModifier`], which can represent every
+/// attribute of a [`light::StaticStateModifier`] as well as increments and alerts. States of
+/// lights in scenes are decoded into a [`light::StaticStateModifier`].
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operation {
+    LightAttribute {
+        id: String,
+        modifier: light::AttributeModifier,
+    },
+    LightState {
+        id: String,
+        modifier: light::StateModifier,
+    },
+    SearchLights(light::Scanner),
+    CreateGroup(group::Creator),
+    GroupAttribute {
+        id: String,
+        modifier: group::AttributeModifier,
+    },
+    GroupState {
+        id: String,
+        modifier: group::StateModifier,
+    },
+    CreateScene(scene::Creator),
+    Scene {
+        id: String,
+        modifier: scene::Modifier,
+    },
+    SceneLightState {
+        id: String,
+        light_id: String,
+        modifier: light::StaticStateModifier,
+    },
+    CreateSchedule(schedule::Creator),
+    Schedule {
+        id: String,
+        modifier: schedule::Modifier,
+    },
+    CreateRule(rule::Creator),
+    Rule {
+        id: String,
+        modifier: rule::Modifier,
+    },
+    CreateSensor(sensor::Creator),
+    SensorAttribute {
+        id: String,
+        modifier: sensor::AttributeModifier,
+    },
+    SensorState {
+        id: String,
+        modifier: sensor::StateModifier,
+    },
+    SensorConfig {
+        id: String,
+        modifier: sensor::ConfigModifier,
+    },
+    SearchSensors(sensor::Scanner),
+    CreateResourcelink(resourcelink::Creator),
+    Resourcelink {
+        id: String,
+        modifier: resourcelink::Modifier,
+    },
+    Delete {
+        kind: resourcelink::LinkKind,
+        id: String,
+    },
+    /// An operation that could not be decoded into one of the other variants.
+    Unknown {
+        address: String,
+        request_method: RequestMethod,
+        body: JsonValue,
+    },
+}
+
+impl Operation {
+    /// Decodes the address, method and body of a request into a typed operation.
+    ///
+    /// The address must not contain the `/api/<username>` prefix. If the body contains attributes
+    /// that the matching type does not know of, [`Operation::Unknown`] is returned so that no
+    /// information is lost.
+    pub fn decode(address: &str, request_method: RequestMethod, body: &JsonValue) -> Self {
+        fn parse<T: DeserializeOwned + Serialize>(body: &JsonValue) -> Option<T> {
+            let value: T = serde_json::from_value(body.clone()).ok()?;
+            let keys = |json: &JsonValue| {
+                json.as_object()
+                    .map(|v| v.keys().cloned().collect::<BTreeSet<_>>())
+            };
+            match keys(&serde_json::to_value(&value).ok()?) == keys(body) {
+                true => Some(value),
+                false => None,
+            }
+        }
+        let segments: Vec<&str> = address.trim_start_matches('/').split('/').collect();
+        let operation = match (request_method, segments.as_slice()) {
+            (RequestMethod::Put, ["lights", id]) => {
+                parse(body).map(|modifier| Self::LightAttribute {
+                    id: id.to_string(),
+                    modifier,
+                })
+            }
+            (RequestMethod::Put, ["lights", id, "state"]) => {
+                parse(body).map(|modifier| Self::LightState {
+                    id: id.to_string(),
+                    modifier,
+                })
+            }
+            (RequestMethod::Post, ["lights"]) => parse(body).map(Self::SearchLights),
+            (RequestMethod::Post, ["groups"]) => parse(body).map(Self::CreateGroup),
+            (RequestMethod::Put, ["groups", id]) => {
+                parse(body).map(|modifier| Self::GroupAttribute {
+                    id: id.to_string(),
+                    modifier,
+                })
+            }
+            (RequestMethod::Put, ["groups", id, "action"]) => {
+                parse(body).map(|modifier| Self::GroupState {
+                    id: id.to_string(),
+                    modifier,
+                })
+            }
+            (RequestMethod::Post, ["scenes"]) => parse(body).map(Self::CreateScene),
+            (RequestMethod::Put, ["scenes", id]) => parse(body).map(|modifier| Self::Scene {
+                id: id.to_string(),
+                modifier,
+            }),
+            (RequestMethod::Put, ["scenes", id, "lightstates", light_id]) => {
+                parse(body).map(|modifier| Self::SceneLightState {
+                    id: id.to_string(),
+                    light_id: light_id.to_string(),
+                    modifier,
+                })
+            }
+            (RequestMethod::Post, ["schedules"]) => parse(body).map(Self::CreateSchedule),
+            (RequestMethod::Put, ["schedules", id]) => parse(body).map(|modifier| Self::Schedule {
+                id: id.to_string(),
+                modifier,
+            }),
+            (RequestMethod::Post, ["rules"]) => parse(body).map(Self::CreateRule),
+            (RequestMethod::Put, ["rules", id]) => parse(body).map(|modifier| Self::Rule {
+                id: id.to_string(),
+                modifier,
+            }),
+            (RequestMethod::Post, ["sensors"]) => parse(body)
+                .map(Self::CreateSensor)
+                .or_else(|| parse(body).map(Self::SearchSensors)),
+            (RequestMethod::Put, ["sensors", id]) => {
+                parse(body).map(|modifier| Self::SensorAttribute {
+                    id: id.to_string(),
+                    modifier,
+                })
+            }
+            (RequestMethod::Put, ["sensors", id, "state"]) => {
+                parse(body).map(|modifier| Self::SensorState {
+                    id: id.to_string(),
+                    modifier,
+                })
+            }
+            (RequestMethod::Put, ["sensors", id, "config"]) => {
+                parse(body).map(|modifier| Self::SensorConfig {
+                    id: id.to_string(),
+                    modifier,
+                })
+            }
+            (RequestMethod::Post, ["resourcelinks"]) => parse(body).map(Self::CreateResourcelink),
+            (RequestMethod::Put, ["resourcelinks", id]) => {
+                parse(body).map(|modifier| Self::Resourcelink {
+                    id: id.to_string(),
+                    modifier,
+                })
+            }
+            (RequestMethod::Delete, [kind, id]) => {
+                resourcelink::LinkKind::from_str(kind).map(|kind| Self::Delete {
+                    kind,
+                    id: id.to_string(),
+                })
+            }
+            _ => None,
+        };
+        operation.unwrap_or_else(|| Self::Unknown {
+            address: address.to_owned(),
+            request_method,
+            body: body.clone(),
+        })
+    }
+}
+
 /// Marker trait for resources.
 pub trait Resource {}
 
@@ -283,6 +648,58 @@ mod tests {
     use chrono::{NaiveDate, NaiveTime};
     use serde_json::json;
 
+    #[test]
+    fn convert_brightness() {
+        assert_eq!(Brightness::from_raw(0), Brightness::MIN);
+        assert_eq!(Brightness::from_raw(255), Brightness::MAX);
+        assert_eq!(Brightness::from_percent(0.0), Brightness::MIN);
+        assert_eq!(Brightness::from_percent(100.0), Brightness::MAX);
+        assert_eq!(Brightness::from_percent(50.0).raw(), 128);
+        assert_eq!(Brightness::from_perceptual(50.0).raw(), 56);
+        assert!((Brightness::from_perceptual(50.0).perceptual() - 50.0).abs() < 1.0);
+
+        let capabilities = light::ControlCapabilities {
+            min_dimlevel: Some(2000),
+            max_lumen: None,
+            color_gamut: None,
+            color_gamut_type: None,
+            color_temperature: None,
+        };
+        assert_eq!(
+            Brightness::from_percent_for(10.0, &capabilities),
+            Brightness::MIN
+        );
+        assert_eq!(Brightness::from_percent_for(60.0, &capabilities).raw(), 128);
+        assert_eq!(
+            Adjust::from(Brightness::MAX),
+            Adjust::Override(Brightness::MAX.raw())
+        );
+    }
+
+    #[test]
+    fn convert_transition_time() {
+        let value = TransitionTime::from_duration(Duration::from_millis(1249)).unwrap();
+        assert_eq!(value.raw(), 12);
+        assert_eq!(value.duration(), Duration::from_millis(1200));
+        let value = TransitionTime::from_duration(Duration::from_millis(1250)).unwrap();
+        assert_eq!(value, TransitionTime::from_raw(13));
+        let max = TransitionTime::MAX.duration();
+        assert_eq!(TransitionTime::from_duration(max), Ok(TransitionTime::MAX));
+        let duration = max + Duration::from_millis(50);
+        assert_eq!(
+            TransitionTime::from_duration(duration),
+            Err(TransitionTimeError(duration))
+        );
+        assert_eq!(
+            TransitionTime::try_from(duration),
+            Err(TransitionTimeError(duration))
+        );
+        assert_eq!(
+            TransitionTime::try_from(Duration::from_millis(450)),
+            Ok(TransitionTime::from_raw(5))
+        );
+    }
+
     #[test]
     fn deserialize_last_scan() {
         let json = json!("none");
@@ -295,8 +712,8 @@ mod tests {
 
         let json = json!("2020-01-01T00:10:00");
         let value: LastScan = serde_json::from_value(json).unwrap();
-        let date = NaiveDate::from_ymd(2020, 1, 1);
-        let time = NaiveTime::from_hms(0, 10, 0);
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let time = NaiveTime::from_hms_opt(0, 10, 0).unwrap();
         assert_eq!(value, LastScan::DateTime(NaiveDateTime::new(date, time)))
     }
 
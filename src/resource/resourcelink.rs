@@ -91,7 +91,7 @@ pub enum LinkKind {
 }
 
 impl LinkKind {
-    fn from_str(value: &str) -> Option<Self> {
+    pub(crate) fn from_str(value: &str) -> Option<Self> {
         match value {
             "groups" => Some(Self::Group),
             "lights" => Some(Self::Light),
@@ -104,7 +104,7 @@ impl LinkKind {
         }
     }
 
-    fn as_str(&self) -> &str {
+    pub(crate) fn as_str(&self) -> &str {
         match self {
             Self::Group => "groups",
             Self::Light => "lights",
@@ -118,7 +118,7 @@ impl LinkKind {
 }
 
 /// Struct for creating a resourcelink.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Creator {
     /// Sets the name of the resourcelink.
@@ -167,7 +167,7 @@ impl resource::Creator for Creator {
 }
 
 /// Modifier for a resourcelink.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Modifier {
     /// Sets the name of the resourcelink.
@@ -1,8 +1,14 @@
-use crate::{resource, util};
-use chrono::NaiveDateTime;
+use crate::resource::{
+    self,
+    schedule::{TimePattern, ValidateTimePatternError},
+};
+use crate::util;
+use chrono::{Duration, NaiveDateTime, NaiveTime};
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use serde_json::{Error as JsonError, Value as JsonValue};
+use std::fmt;
+use thiserror::Error as ThisError;
 
 /// A rule for resources on a bridge.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -70,8 +76,409 @@ pub struct Condition {
     pub value: Option<String>,
 }
 
+impl Condition {
+    /// Creates a new condition from typed parts.
+    ///
+    /// Returns an error if the operator cannot be used with the address or the value.
+    ///
+    /// # Examples
+    ///
+    /// Create a condition that triggers when the first button of a switch is released:
+    /// ```
+    /// use huelib::resource::rule::{
+    ///     Address, Condition, ConditionOperator, ConditionValue, SensorAttribute,
+    /// };
+    ///
+    /// let condition = Condition::new(
+    ///     Address::sensor("5", SensorAttribute::ButtonEvent),
+    ///     ConditionOperator::Equals,
+    ///     Some(ConditionValue::Int(1002)),
+    /// )?;
+    /// assert_eq!(condition.address, "/sensors/5/state/buttonevent");
+    /// # Ok::<(), huelib::resource::rule::InvalidConditionError>(())
+    /// ```
+    pub fn new(
+        address: Address,
+        operator: ConditionOperator,
+        value: Option<ConditionValue>,
+    ) -> Result<Self, InvalidConditionError> {
+        check_condition(&address, operator, value.as_ref())?;
+        Ok(Self {
+            address: address.to_string(),
+            operator,
+            value: value.map(|v| v.to_string()),
+        })
+    }
+
+    /// Creates a condition that checks whether an attribute equals a value.
+    pub fn equals(address: Address, value: ConditionValue) -> Result<Self, InvalidConditionError> {
+        Self::new(address, ConditionOperator::Equals, Some(value))
+    }
+
+    /// Creates a condition that checks whether an attribute is less than a value.
+    pub fn less_than(
+        address: Address,
+        value: ConditionValue,
+    ) -> Result<Self, InvalidConditionError> {
+        Self::new(address, ConditionOperator::LessThan, Some(value))
+    }
+
+    /// Creates a condition that checks whether an attribute is greater than a value.
+    pub fn greater_than(
+        address: Address,
+        value: ConditionValue,
+    ) -> Result<Self, InvalidConditionError> {
+        Self::new(address, ConditionOperator::GreaterThan, Some(value))
+    }
+
+    /// Creates a condition that triggers when an attribute changes.
+    pub fn changed(address: Address) -> Result<Self, InvalidConditionError> {
+        Self::new(address, ConditionOperator::Dx, None)
+    }
+
+    /// Creates a condition that triggers when an attribute changed and then stayed unchanged for
+    /// the given duration.
+    pub fn changed_delayed(
+        address: Address,
+        duration: Duration,
+    ) -> Result<Self, InvalidConditionError> {
+        Self::new(
+            address,
+            ConditionOperator::Ddx,
+            Some(ConditionValue::Duration(duration)),
+        )
+    }
+
+    /// Creates a condition that checks whether an attribute has not changed for the given
+    /// duration.
+    pub fn stable(address: Address, duration: Duration) -> Result<Self, InvalidConditionError> {
+        Self::new(
+            address,
+            ConditionOperator::Stable,
+            Some(ConditionValue::Duration(duration)),
+        )
+    }
+
+    /// Creates a condition that checks whether an attribute has changed within the given
+    /// duration.
+    pub fn not_stable(address: Address, duration: Duration) -> Result<Self, InvalidConditionError> {
+        Self::new(
+            address,
+            ConditionOperator::NotStable,
+            Some(ConditionValue::Duration(duration)),
+        )
+    }
+
+    /// Creates a condition that checks whether the local time of the bridge is in a time
+    /// interval.
+    pub fn in_interval(start: NaiveTime, end: NaiveTime) -> Self {
+        Self {
+            address: Address::Config(ConfigAttribute::LocalTime).to_string(),
+            operator: ConditionOperator::In,
+            value: Some(ConditionValue::TimeInterval(start, end).to_string()),
+        }
+    }
+
+    /// Creates a condition that checks whether the local time of the bridge is not in a time
+    /// interval.
+    pub fn not_in_interval(start: NaiveTime, end: NaiveTime) -> Self {
+        Self {
+            operator: ConditionOperator::NotIn,
+            ..Self::in_interval(start, end)
+        }
+    }
+}
+
+fn check_condition(
+    address: &Address,
+    operator: ConditionOperator,
+    value: Option<&ConditionValue>,
+) -> Result<(), InvalidConditionError> {
+    use ConditionOperator::*;
+    let value_type = address.value_type();
+    let operator_supported = match operator {
+        Equals => matches!(value_type, ValueType::Bool | ValueType::Int),
+        LessThan | GreaterThan => matches!(value_type, ValueType::Int | ValueType::Timestamp),
+        Dx | Ddx | Stable | NotStable => value_type != ValueType::Time,
+        In | NotIn => value_type == ValueType::Time,
+    };
+    if !operator_supported {
+        return Err(InvalidConditionError::IncompatibleOperator {
+            address: address.to_string(),
+            operator,
+        });
+    }
+    let value = match (operator, value) {
+        (Dx, None) => return Ok(()),
+        (Dx, Some(_)) => return Err(InvalidConditionError::UnexpectedValue(operator)),
+        (_, None) => return Err(InvalidConditionError::MissingValue(operator)),
+        (_, Some(v)) => v,
+    };
+    let value_supported = match operator {
+        Equals | LessThan | GreaterThan => matches!(
+            (value_type, value),
+            (ValueType::Bool, ConditionValue::Bool(_))
+                | (ValueType::Int, ConditionValue::Int(_))
+                | (ValueType::Timestamp, ConditionValue::Timestamp(_))
+        ),
+        Ddx | Stable | NotStable => matches!(value, ConditionValue::Duration(_)),
+        In | NotIn => matches!(value, ConditionValue::TimeInterval(..)),
+        Dx => false,
+    };
+    if !value_supported {
+        return Err(InvalidConditionError::IncompatibleValue {
+            operator,
+            value: value.clone(),
+        });
+    }
+    if let ConditionValue::Duration(duration) = value {
+        TimePattern::Timer {
+            duration: *duration,
+            random: None,
+        }
+        .validate()?;
+    }
+    Ok(())
+}
+
+/// Address of a resource attribute that is used in a condition.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Address {
+    /// Attribute of a sensor.
+    Sensor {
+        /// Identifier of the sensor.
+        id: String,
+        /// Attribute of the sensor.
+        attribute: SensorAttribute,
+    },
+    /// Attribute of a group.
+    Group {
+        /// Identifier of the group.
+        id: String,
+        /// Attribute of the group.
+        attribute: GroupAttribute,
+    },
+    /// Attribute of the bridge configuration.
+    Config(ConfigAttribute),
+}
+
+impl Address {
+    /// Creates a new address of a sensor attribute.
+    pub fn sensor<S: Into<String>>(id: S, attribute: SensorAttribute) -> Self {
+        Self::Sensor {
+            id: id.into(),
+            attribute,
+        }
+    }
+
+    /// Creates a new address of a group attribute.
+    pub fn group<S: Into<String>>(id: S, attribute: GroupAttribute) -> Self {
+        Self::Group {
+            id: id.into(),
+            attribute,
+        }
+    }
+
+    fn value_type(&self) -> ValueType {
+        match self {
+            Self::Sensor { attribute, .. } => attribute.value_type(),
+            Self::Group { .. } => ValueType::Bool,
+            Self::Config(ConfigAttribute::LocalTime) => ValueType::Time,
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sensor { id, attribute } => write!(f, "/sensors/{}/{}", id, attribute.path()),
+            Self::Group { id, attribute } => write!(f, "/groups/{}/{}", id, attribute.path()),
+            Self::Config(ConfigAttribute::LocalTime) => write!(f, "/config/localtime"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ValueType {
+    Bool,
+    Int,
+    Timestamp,
+    Time,
+}
+
+/// Attribute of a sensor that can be used in a condition.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SensorAttribute {
+    /// Raw code of the last button event.
+    ButtonEvent,
+    /// Whether presence is detected.
+    Presence,
+    /// Light level in `10000 * log10(lux) + 1`.
+    LightLevel,
+    /// Whether the light level is below the dark threshold.
+    Dark,
+    /// Whether it is daylight.
+    Daylight,
+    /// Temperature in hundredths of a degree Celsius.
+    Temperature,
+    /// Relative humidity in hundredths of a percent.
+    Humidity,
+    /// Flag of a generic flag sensor.
+    Flag,
+    /// Status of a generic status sensor.
+    Status,
+    /// Whether an open/close sensor is open.
+    Open,
+    /// When the state was last updated.
+    LastUpdated,
+    /// Whether the sensor is on.
+    On,
+    /// Whether the sensor can be reached by the bridge.
+    Reachable,
+    /// Battery state in percent.
+    Battery,
+}
+
+impl SensorAttribute {
+    fn path(&self) -> &'static str {
+        match self {
+            Self::ButtonEvent => "state/buttonevent",
+            Self::Presence => "state/presence",
+            Self::LightLevel => "state/lightlevel",
+            Self::Dark => "state/dark",
+            Self::Daylight => "state/daylight",
+            Self::Temperature => "state/temperature",
+            Self::Humidity => "state/humidity",
+            Self::Flag => "state/flag",
+            Self::Status => "state/status",
+            Self::Open => "state/open",
+            Self::LastUpdated => "state/lastupdated",
+            Self::On => "config/on",
+            Self::Reachable => "config/reachable",
+            Self::Battery => "config/battery",
+        }
+    }
+
+    fn value_type(&self) -> ValueType {
+        match self {
+            Self::Presence
+            | Self::Dark
+            | Self::Daylight
+            | Self::Flag
+            | Self::Open
+            | Self::On
+            | Self::Reachable => ValueType::Bool,
+            Self::ButtonEvent
+            | Self::LightLevel
+            | Self::Temperature
+            | Self::Humidity
+            | Self::Status
+            | Self::Battery => ValueType::Int,
+            Self::LastUpdated => ValueType::Timestamp,
+        }
+    }
+}
+
+/// Attribute of a group that can be used in a condition.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum GroupAttribute {
+    /// Whether any light of the group is on.
+    AnyOn,
+    /// Whether all lights of the group are on.
+    AllOn,
+}
+
+impl GroupAttribute {
+    fn path(&self) -> &'static str {
+        match self {
+            Self::AnyOn => "state/any_on",
+            Self::AllOn => "state/all_on",
+        }
+    }
+}
+
+/// Attribute of the bridge configuration that can be used in a condition.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ConfigAttribute {
+    /// Local time of the bridge.
+    LocalTime,
+}
+
+/// Value of a condition.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ConditionValue {
+    /// Boolean value, used with the `eq` operator.
+    Bool(bool),
+    /// Integer value, used with the `eq`, `lt` and `gt` operators.
+    Int(i32),
+    /// Timestamp, used with the `lt` and `gt` operators.
+    Timestamp(NaiveDateTime),
+    /// Duration, used with the `ddx`, `stable` and `not stable` operators.
+    Duration(Duration),
+    /// Time interval from the first to the second time, used with the `in` and `not in`
+    /// operators.
+    TimeInterval(NaiveTime, NaiveTime),
+}
+
+impl fmt::Display for ConditionValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(v) => write!(f, "{}", v),
+            Self::Int(v) => write!(f, "{}", v),
+            Self::Timestamp(v) => write!(f, "{}", v.format("%Y-%m-%dT%H:%M:%S")),
+            Self::Duration(v) => write!(
+                f,
+                "{}",
+                TimePattern::Timer {
+                    duration: *v,
+                    random: None,
+                }
+            ),
+            Self::TimeInterval(start, end) => write!(
+                f,
+                "{}",
+                TimePattern::Interval {
+                    weekdays: None,
+                    start: *start,
+                    end: *end,
+                }
+            ),
+        }
+    }
+}
+
+/// Error that occurs when the parts of a condition are not compatible.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum InvalidConditionError {
+    /// The operator cannot be used with the attribute.
+    #[error("Operator `{operator:?}` cannot be used with `{address}`")]
+    IncompatibleOperator {
+        /// Address of the attribute.
+        address: String,
+        /// Operator of the condition.
+        operator: ConditionOperator,
+    },
+    /// The value cannot be used with the operator and attribute.
+    #[error("Value `{value}` cannot be used with operator `{operator:?}`")]
+    IncompatibleValue {
+        /// Operator of the condition.
+        operator: ConditionOperator,
+        /// Value of the condition.
+        value: ConditionValue,
+    },
+    /// The operator requires a value.
+    #[error("Operator `{0:?}` requires a value")]
+    MissingValue(ConditionOperator),
+    /// The operator does not take a value.
+    #[error("Operator `{0:?}` does not take a value")]
+    UnexpectedValue(ConditionOperator),
+    /// The value cannot be formatted as a time pattern accepted by the bridge.
+    #[error("Invalid time pattern: {0}")]
+    InvalidTimePattern(#[from] ValidateTimePatternError),
+}
+
 /// Condition operator of a rule.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub enum ConditionOperator {
     /// Less than an int value.
     #[serde(rename = "lt")]
@@ -156,6 +563,27 @@ impl Action {
             body: serde_json::to_value(scanner)?,
         })
     }
+
+    /// Creates a new action that deletes a resource.
+    pub fn delete(kind: resource::resourcelink::LinkKind, id: String) -> Self {
+        Self {
+            address: format!("/{}/{}", kind.as_str(), id),
+            request_method: ActionRequestMethod::Delete,
+            body: JsonValue::Object(Default::default()),
+        }
+    }
+
+    /// Decodes the action into a typed [`Operation`].
+    ///
+    /// [`Operation`]: resource::Operation
+    pub fn operation(&self) -> resource::Operation {
+        let request_method = match self.request_method {
+            ActionRequestMethod::Put => resource::RequestMethod::Put,
+            ActionRequestMethod::Post => resource::RequestMethod::Post,
+            ActionRequestMethod::Delete => resource::RequestMethod::Delete,
+        };
+        resource::Operation::decode(&self.address, request_method, &self.body)
+    }
 }
 
 /// Request method of an action.
@@ -169,7 +597,7 @@ pub enum ActionRequestMethod {
 }
 
 /// Struct for creating a rule.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Setters)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Creator {
     /// Sets the name of the rule.
@@ -205,7 +633,7 @@ impl resource::Creator for Creator {
 }
 
 /// Struct for modifying a rule.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Setters)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Modifier {
     /// Sets the name of the modifier.
@@ -292,6 +720,165 @@ mod tests {
             "body": {}
         });
         assert_eq!(action_json, expected_json);
+
+        let action = Action::delete(resource::resourcelink::LinkKind::Scene, "abc".into());
+        let action_json = serde_json::to_value(action).unwrap();
+        let expected_json = json!({
+            "address": "/scenes/abc",
+            "method": "DELETE",
+            "body": {}
+        });
+        assert_eq!(action_json, expected_json);
+    }
+
+    #[test]
+    fn decode_action() {
+        use resource::{group, light, sensor, Adjust, Operation, RequestMethod};
+
+        let modifier = light::StateModifier::new()
+            .with_on(true)
+            .with_brightness(Adjust::Decrement(30))
+            .with_color_space_coordinates(Adjust::Override((0.5, 0.25)));
+        let action = Action::from_modifier(&modifier, "1".into()).unwrap();
+        let expected = Operation::LightState {
+            id: "1".into(),
+            modifier,
+        };
+        assert_eq!(action.operation(), expected);
+
+        let modifier = group::StateModifier::new().with_scene("abc".into());
+        let action = Action::from_modifier(&modifier, "0".into()).unwrap();
+        let expected = Operation::GroupState {
+            id: "0".into(),
+            modifier,
+        };
+        assert_eq!(action.operation(), expected);
+
+        let modifier = sensor::StateModifier::new().with_status(2);
+        let action = Action::from_modifier(&modifier, "5".into()).unwrap();
+        let expected = Operation::SensorState {
+            id: "5".into(),
+            modifier,
+        };
+        assert_eq!(action.operation(), expected);
+
+        let creator = group::Creator::new("test".into(), vec!["1".into()]);
+        let action = Action::from_creator(&creator).unwrap();
+        assert_eq!(action.operation(), Operation::CreateGroup(creator));
+
+        let action = Action {
+            address: "/scenes/abc/lightstates/1".into(),
+            request_method: ActionRequestMethod::Put,
+            body: json!({"on": true, "bri": 100}),
+        };
+        let expected = Operation::SceneLightState {
+            id: "abc".into(),
+            light_id: "1".into(),
+            modifier: light::StaticStateModifier::new()
+                .with_on(true)
+                .with_brightness(100),
+        };
+        assert_eq!(action.operation(), expected);
+
+        let action = Action {
+            address: "/lights/1/state".into(),
+            request_method: ActionRequestMethod::Put,
+            body: json!({"on": true, "unknown": 1}),
+        };
+        let expected = Operation::Unknown {
+            address: "/lights/1/state".into(),
+            request_method: RequestMethod::Put,
+            body: json!({"on": true, "unknown": 1}),
+        };
+        assert_eq!(action.operation(), expected);
+    }
+
+    #[test]
+    fn create_condition() {
+        let address = Address::sensor("5", SensorAttribute::ButtonEvent);
+        let condition = Condition::equals(address.clone(), ConditionValue::Int(1002)).unwrap();
+        let condition_json = serde_json::to_value(condition).unwrap();
+        let expected_json = json!({
+            "address": "/sensors/5/state/buttonevent",
+            "operator": "eq",
+            "value": "1002"
+        });
+        assert_eq!(condition_json, expected_json);
+
+        let condition = Condition::stable(
+            Address::sensor("2", SensorAttribute::Presence),
+            Duration::seconds(3725),
+        )
+        .unwrap();
+        assert_eq!(condition.value, Some("PT01:02:05".into()));
+
+        let condition = Condition::not_in_interval(
+            NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(7, 30, 0).unwrap(),
+        );
+        let condition_json = serde_json::to_value(condition).unwrap();
+        let expected_json = json!({
+            "address": "/config/localtime",
+            "operator": "not in",
+            "value": "T22:00:00/T07:30:00"
+        });
+        assert_eq!(condition_json, expected_json);
+
+        let condition = Condition::changed(Address::group("1", GroupAttribute::AnyOn)).unwrap();
+        assert_eq!(condition.address, "/groups/1/state/any_on");
+        assert_eq!(condition.value, None);
+    }
+
+    #[test]
+    fn check_condition() {
+        let address = Address::sensor("5", SensorAttribute::Presence);
+        assert_eq!(
+            Condition::new(address.clone(), ConditionOperator::Equals, None),
+            Err(InvalidConditionError::MissingValue(
+                ConditionOperator::Equals
+            ))
+        );
+        assert_eq!(
+            Condition::equals(address.clone(), ConditionValue::Int(1)),
+            Err(InvalidConditionError::IncompatibleValue {
+                operator: ConditionOperator::Equals,
+                value: ConditionValue::Int(1),
+            })
+        );
+        assert_eq!(
+            Condition::greater_than(address.clone(), ConditionValue::Int(1)),
+            Err(InvalidConditionError::IncompatibleOperator {
+                address: "/sensors/5/state/presence".into(),
+                operator: ConditionOperator::GreaterThan,
+            })
+        );
+        assert_eq!(
+            Condition::new(
+                address,
+                ConditionOperator::Dx,
+                Some(ConditionValue::Bool(true))
+            ),
+            Err(InvalidConditionError::UnexpectedValue(
+                ConditionOperator::Dx
+            ))
+        );
+        assert!(Condition::changed(Address::Config(ConfigAttribute::LocalTime)).is_err());
+        let address = Address::sensor("5", SensorAttribute::Presence);
+        assert_eq!(
+            Condition::changed_delayed(address.clone(), Duration::seconds(-1)),
+            Err(InvalidConditionError::InvalidTimePattern(
+                ValidateTimePatternError::DurationOutOfRange
+            ))
+        );
+        assert_eq!(
+            Condition::stable(address.clone(), Duration::hours(200)),
+            Err(InvalidConditionError::InvalidTimePattern(
+                ValidateTimePatternError::DurationOutOfRange
+            ))
+        );
+        assert!(
+            Condition::not_stable(address, Duration::seconds(99 * 3600 + 59 * 60 + 59)).is_ok()
+        );
     }
 
     #[test]
@@ -3,10 +3,11 @@ use crate::util;
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use serde_repr::Deserialize_repr;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
 
 /// A scene.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Scene {
     /// Identifier of the scene.
     #[serde(skip_deserializing)]
@@ -44,12 +45,65 @@ pub struct Scene {
     pub last_update: Option<chrono::NaiveDateTime>,
     /// Version of the scene document.
     pub version: Version,
+    /// States of the lights in the scene.
+    ///
+    /// The keys of the HashMap are the light identifiers. Only available with an individual scene
+    /// resource.
+    #[serde(rename = "lightstates")]
+    pub light_states: Option<HashMap<String, light::StaticStateModifier>>,
 }
 
 impl Scene {
     pub(crate) fn with_id(self, id: String) -> Self {
         Self { id, ..self }
     }
+
+    /// Returns the fields that are compared and hashed.
+    ///
+    /// The color space coordinates of the light states are compared by their bits, so that the
+    /// equality of scenes is total even if a coordinate is NaN.
+    fn key(&self) -> impl Eq + Hash + '_ {
+        let light_states = self.light_states.as_ref().map(|states| {
+            states
+                .iter()
+                .map(|(id, state)| {
+                    let coordinates = state
+                        .color_space_coordinates
+                        .map(|(x, y)| (x.to_bits(), y.to_bits()));
+                    let state = (
+                        state.on,
+                        state.brightness,
+                        state.hue,
+                        state.saturation,
+                        coordinates,
+                        state.color_temperature,
+                        state.effect,
+                        state.transition_time,
+                    );
+                    (id, state)
+                })
+                .collect::<BTreeMap<_, _>>()
+        });
+        (
+            (&self.id, &self.name, self.kind, &self.group, &self.lights),
+            (&self.owner, self.recycle, self.locked, &self.app_data),
+            (&self.picture, self.last_update, self.version, light_states),
+        )
+    }
+}
+
+impl PartialEq for Scene {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Scene {}
+
+impl Hash for Scene {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
 }
 
 impl resource::Resource for Scene {}
@@ -85,7 +139,7 @@ pub enum Version {
 }
 
 /// Struct for creating a scene.
-#[derive(Clone, Debug, PartialEq, Serialize, Setters)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Creator {
     /// Sets the name of the scene.
@@ -125,7 +179,7 @@ impl resource::Creator for Creator {
 }
 
 /// Struct for modifying a scene.
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Modifier {
     /// Sets the name of the scene.
@@ -163,6 +217,47 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn deserialize_scene() {
+        let json = json!({
+            "name": "Evening",
+            "type": "LightScene",
+            "lights": ["1"],
+            "owner": "user",
+            "recycle": false,
+            "locked": false,
+            "appdata": {},
+            "picture": "",
+            "lastupdate": "2020-01-01T00:00:00",
+            "version": 2,
+            "lightstates": {"1": {"on": true, "bri": 100, "xy": [0.3, 0.3]}}
+        });
+        let scene: Scene = serde_json::from_value(json).unwrap();
+        let light_states = scene.light_states.as_ref().unwrap();
+        assert_eq!(
+            light_states["1"],
+            light::StaticStateModifier::new()
+                .with_on(true)
+                .with_brightness(100)
+                .with_color_space_coordinates((0.3, 0.3))
+        );
+        let scenes: std::collections::HashSet<_> =
+            vec![scene.clone(), scene.clone()].into_iter().collect();
+        assert_eq!(scenes.len(), 1);
+
+        let mut nan_scene = scene.clone();
+        nan_scene.light_states = Some(
+            vec![(
+                "1".to_owned(),
+                light::StaticStateModifier::new().with_color_space_coordinates((f32::NAN, 0.3)),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(nan_scene, nan_scene.clone());
+        assert_ne!(nan_scene, scene);
+    }
+
     #[test]
     fn serialize_creator() {
         let creator = Creator::new("test".into(), vec!["1".into()]);
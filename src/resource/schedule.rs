@@ -1,8 +1,12 @@
-use crate::resource;
-use chrono::NaiveDateTime;
+use crate::resource::{self, Config};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDateTime, NaiveTime, TimeZone, Weekday,
+};
 use derive_setters::Setters;
-use serde::{Deserialize, Serialize};
+use serde::{de, ser, Deserialize, Serialize};
 use serde_json::{Error as JsonError, Value as JsonValue};
+use std::{convert::TryFrom, fmt, ops::BitOr, str::FromStr};
+use thiserror::Error as ThisError;
 
 /// Schedule of a resource.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -18,7 +22,7 @@ pub struct Schedule {
     pub command: Command,
     /// Time when the scheduled event will occur.
     #[serde(rename = "localtime")]
-    pub local_time: String,
+    pub local_time: TimePattern,
     /// UTC time that the timer was started. Only provided for timers.
     #[serde(rename = "starttime")]
     pub start_time: Option<NaiveDateTime>,
@@ -33,6 +37,168 @@ impl Schedule {
     pub(crate) fn with_id(self, id: String) -> Self {
         Self { id, ..self }
     }
+
+    /// Returns an iterator over the times when the schedule fires after the current time of the
+    /// bridge.
+    ///
+    /// The times are in the local time of the bridge, see [`Config::utc_offset`]. The
+    /// randomization of the time pattern is not included, so the schedule may fire later than
+    /// the returned times. Disabled schedules never fire.
+    ///
+    /// All times use the current offset of the bridge, because the timezone of the bridge is not
+    /// resolved. The local time of each fire time is correct, but after a change of daylight
+    /// saving time the offset, and with that the UTC time, is off by the shift of the change. Use
+    /// [`DateTime::naive_local`] if only the wall-clock time is needed.
+    pub fn fire_times(&self, config: &Config) -> impl Iterator<Item = DateTime<FixedOffset>> + '_ {
+        self.fire_times_from(config.current_time, config.utc_offset())
+    }
+
+    /// Returns the next `count` times when the schedule fires.
+    ///
+    /// See [`fire_times`](Self::fire_times) for details.
+    ///
+    /// # Examples
+    ///
+    /// Print the next wake-up alarm:
+    /// ```no_run
+    /// # use huelib::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// let config = bridge.get_config()?;
+    /// let schedule = bridge.get_schedule("1")?;
+    /// match schedule.next_fire_times(&config, 1).first() {
+    ///     Some(v) => println!("Next wake-up alarm at {}", v),
+    ///     None => println!("The schedule will never fire again"),
+    /// }
+    /// # Ok::<(), huelib::Error>(())
+    /// ```
+    pub fn next_fire_times(&self, config: &Config, count: usize) -> Vec<DateTime<FixedOffset>> {
+        self.fire_times(config).take(count).collect()
+    }
+
+    /// Returns the times when the schedule fires within the given window.
+    ///
+    /// Both the start and the end of the window are inclusive.
+    pub fn fire_times_between(
+        &self,
+        config: &Config,
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+    ) -> Vec<DateTime<FixedOffset>> {
+        self.fire_times(config)
+            .skip_while(|v| *v < start)
+            .take_while(|v| *v <= end)
+            .collect()
+    }
+
+    /// Returns whether the schedule fires again.
+    pub fn will_fire(&self, config: &Config) -> bool {
+        self.fire_times(config).next().is_some()
+    }
+
+    fn fire_times_from(
+        &self,
+        now: NaiveDateTime,
+        offset: FixedOffset,
+    ) -> Box<dyn Iterator<Item = DateTime<FixedOffset>> + '_> {
+        let local_now = now + Duration::seconds(offset.local_minus_utc() as i64);
+        let from_local = move |v: NaiveDateTime| offset.from_local_datetime(&v).single();
+        let from_utc = move |v: NaiveDateTime| offset.from_utc_datetime(&v);
+        if self.status == Status::Disabled {
+            return Box::new(std::iter::empty());
+        }
+        match &self.local_time {
+            TimePattern::Absolute { time, .. } => Box::new(
+                Some(*time)
+                    .filter(|v| *v > local_now)
+                    .and_then(from_local)
+                    .into_iter(),
+            ),
+            TimePattern::Recurring { weekdays, time, .. } => {
+                if weekdays.bits() == 0 {
+                    return Box::new(std::iter::empty());
+                }
+                let (weekdays, time) = (*weekdays, *time);
+                Box::new(
+                    (0..)
+                        .map(move |v| local_now.date() + Duration::days(v))
+                        .filter(move |v| weekdays.contains_date(v))
+                        .map(move |v| v.and_time(time))
+                        .filter(move |v| *v > local_now)
+                        .filter_map(from_local),
+                )
+            }
+            TimePattern::Timer { duration, .. } => Box::new(
+                self.start_time
+                    .map(|v| v + *duration)
+                    .filter(|v| *v > now)
+                    .map(from_utc)
+                    .into_iter(),
+            ),
+            TimePattern::RecurringTimer {
+                count, duration, ..
+            } => {
+                let (start_time, duration) = match self.start_time {
+                    Some(v) if *duration > Duration::zero() => (v, *duration),
+                    _ => return Box::new(std::iter::empty()),
+                };
+                // Index of the first expiry after the current time. The division is done in
+                // milliseconds, so the index is corrected by at most one step afterwards.
+                let elapsed = (now - start_time)
+                    .num_milliseconds()
+                    .div_euclid(duration.num_milliseconds().max(1));
+                let mut first = i32::try_from(elapsed.max(0) + 1).unwrap_or(i32::MAX);
+                while first > 1 && start_time + duration * (first - 1) > now {
+                    first -= 1;
+                }
+                while start_time + duration * first <= now {
+                    first += 1;
+                }
+                let times = (first..)
+                    .map(move |v| start_time + duration * v)
+                    .map(from_utc);
+                match count {
+                    Some(count) => {
+                        let remaining = (*count as i32 + 1 - first).max(0);
+                        Box::new(times.take(remaining as usize))
+                    }
+                    None => Box::new(times),
+                }
+            }
+            TimePattern::Interval { .. } | TimePattern::Unknown(_) => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+/// Returns the schedules that fire within the given window together with their fire times.
+///
+/// Both the start and the end of the window are inclusive. See [`Schedule::fire_times`] for
+/// details.
+pub fn fire_within<'a>(
+    schedules: &'a [Schedule],
+    config: &Config,
+    start: DateTime<FixedOffset>,
+    end: DateTime<FixedOffset>,
+) -> Vec<(&'a Schedule, Vec<DateTime<FixedOffset>>)> {
+    schedules
+        .iter()
+        .map(|v| (v, v.fire_times_between(config, start, end)))
+        .filter(|(_, times)| !times.is_empty())
+        .collect()
+}
+
+/// Returns the schedules whose command is sent as a user that is not in the whitelist.
+///
+/// Such schedules fail with an error when they fire. Commands with an address that does not
+/// contain a username are not returned.
+pub fn with_unknown_username<'a>(schedules: &'a [Schedule], config: &Config) -> Vec<&'a Schedule> {
+    schedules
+        .iter()
+        .filter(|schedule| match schedule.command.username() {
+            Some(username) => !config.whitelist.iter().any(|user| user.id == username),
+            None => false,
+        })
+        .collect()
 }
 
 impl resource::Resource for Schedule {}
@@ -94,6 +260,55 @@ impl Command {
             body: serde_json::to_value(scanner)?,
         })
     }
+
+    /// Creates a new command that deletes a resource.
+    pub fn delete<S>(kind: resource::resourcelink::LinkKind, id: String, username: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        Self {
+            address: format!("/api/{}/{}/{}", username.as_ref(), kind.as_str(), id),
+            request_method: CommandRequestMethod::Delete,
+            body: JsonValue::Object(Default::default()),
+        }
+    }
+
+    /// Returns the username that the command is sent as.
+    ///
+    /// Returns `None` if the address does not start with `/api/<username>`.
+    pub fn username(&self) -> Option<&str> {
+        let address = self.address.strip_prefix("/api/")?;
+        let username = address.split('/').next()?;
+        match username.is_empty() {
+            true => None,
+            false => Some(username),
+        }
+    }
+
+    /// Decodes the command into a typed [`Operation`].
+    ///
+    /// The `/api/<username>` prefix of the address is ignored.
+    ///
+    /// [`Operation`]: resource::Operation
+    pub fn operation(&self) -> resource::Operation {
+        let request_method = match self.request_method {
+            CommandRequestMethod::Put => resource::RequestMethod::Put,
+            CommandRequestMethod::Post => resource::RequestMethod::Post,
+            CommandRequestMethod::Delete => resource::RequestMethod::Delete,
+        };
+        let address = match self.address.strip_prefix("/api/") {
+            Some(v) => v.find('/').map_or("", |index| &v[index..]),
+            None => &self.address,
+        };
+        match resource::Operation::decode(address, request_method, &self.body) {
+            resource::Operation::Unknown { .. } => resource::Operation::Unknown {
+                address: self.address.clone(),
+                request_method,
+                body: self.body.clone(),
+            },
+            v => v,
+        }
+    }
 }
 
 /// Request method of an command.
@@ -106,6 +321,392 @@ pub enum CommandRequestMethod {
     Delete,
 }
 
+/// Time pattern of a schedule or a rule condition.
+///
+/// The randomization of a pattern is a maximum duration that is added to the time at random.
+///
+/// # Examples
+///
+/// Parse and format a pattern that triggers on workdays at 7:00:
+/// ```
+/// use huelib::resource::schedule::{TimePattern, Weekdays};
+/// use chrono::NaiveTime;
+///
+/// let pattern: TimePattern = "W124/T07:00:00".parse()?;
+/// assert_eq!(
+///     pattern,
+///     TimePattern::Recurring {
+///         weekdays: Weekdays::WORKDAYS,
+///         time: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+///         random: None,
+///     }
+/// );
+/// assert_eq!(pattern.to_string(), "W124/T07:00:00");
+/// # Ok::<(), huelib::resource::schedule::ParseTimePatternError>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TimePattern {
+    /// Occurs once at the given date and time.
+    Absolute {
+        /// Date and time of the occurrence.
+        time: NaiveDateTime,
+        /// Maximum random duration that is added to the time.
+        random: Option<Duration>,
+    },
+    /// Occurs every week on the given weekdays at the given time.
+    Recurring {
+        /// Weekdays on which the pattern occurs.
+        weekdays: Weekdays,
+        /// Time of the occurrence.
+        time: NaiveTime,
+        /// Maximum random duration that is added to the time.
+        random: Option<Duration>,
+    },
+    /// Occurs once after the given duration.
+    Timer {
+        /// Duration after which the timer expires.
+        duration: Duration,
+        /// Maximum random duration that is added to the duration.
+        random: Option<Duration>,
+    },
+    /// Occurs repeatedly after the given duration.
+    RecurringTimer {
+        /// Number of times the timer is repeated, or `None` to repeat it forever.
+        count: Option<u8>,
+        /// Duration after which the timer expires.
+        duration: Duration,
+        /// Maximum random duration that is added to the duration.
+        random: Option<Duration>,
+    },
+    /// Time interval from the start to the end time.
+    ///
+    /// This is only valid in a rule condition.
+    Interval {
+        /// Weekdays on which the interval is valid, or `None` for every day.
+        weekdays: Option<Weekdays>,
+        /// Start of the interval.
+        start: NaiveTime,
+        /// End of the interval.
+        end: NaiveTime,
+    },
+    /// Pattern that is not supported by this crate.
+    ///
+    /// The pattern is kept as it was returned by the bridge, so that a resource with such a
+    /// pattern can still be deserialized. Parsing a string never returns this variant.
+    Unknown(String),
+}
+
+impl TimePattern {
+    /// Checks whether the pattern can be formatted as a string accepted by the Philips Hue API.
+    ///
+    /// Durations have to be between zero and 99:59:59 and weekdays must contain at least one day.
+    /// Patterns that fail this check cannot be serialized.
+    pub fn validate(&self) -> Result<(), ValidateTimePatternError> {
+        let (weekdays, durations) = match self {
+            Self::Absolute { random, .. } => (None, [None, random.as_ref()]),
+            Self::Recurring {
+                weekdays, random, ..
+            } => (Some(weekdays), [None, random.as_ref()]),
+            Self::Timer { duration, random }
+            | Self::RecurringTimer {
+                duration, random, ..
+            } => (None, [Some(duration), random.as_ref()]),
+            Self::Interval { weekdays, .. } => (weekdays.as_ref(), [None, None]),
+            Self::Unknown(_) => (None, [None, None]),
+        };
+        if matches!(weekdays, Some(v) if v.bits() == 0) {
+            return Err(ValidateTimePatternError::EmptyWeekdays);
+        }
+        let max_duration = Duration::seconds(MAX_DURATION_SECONDS);
+        if durations
+            .iter()
+            .flatten()
+            .any(|v| **v < Duration::zero() || **v > max_duration)
+        {
+            return Err(ValidateTimePatternError::DurationOutOfRange);
+        }
+        Ok(())
+    }
+}
+
+/// Longest duration that fits into the two digit hours of the `hh:mm:ss` format.
+const MAX_DURATION_SECONDS: i64 = 99 * 3600 + 59 * 60 + 59;
+
+fn format_time(time: &NaiveTime) -> impl fmt::Display {
+    time.format("%H:%M:%S")
+}
+
+fn format_duration(duration: &Duration) -> String {
+    let seconds = duration.num_seconds();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn parse_time(s: &str) -> Result<NaiveTime, ParseTimePatternError> {
+    NaiveTime::parse_from_str(s, "%H:%M:%S").map_err(|_| ParseTimePatternError::InvalidTime)
+}
+
+fn parse_duration(s: &str) -> Result<Duration, ParseTimePatternError> {
+    let mut parts = s.split(':').map(|v| {
+        if v.len() == 2 {
+            v.parse::<i64>().ok()
+        } else {
+            None
+        }
+    });
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Some(h)), Some(Some(m)), Some(Some(s)), None) if m < 60 && s < 60 => {
+            Ok(Duration::seconds(h * 3600 + m * 60 + s))
+        }
+        _ => Err(ParseTimePatternError::InvalidDuration),
+    }
+}
+
+impl fmt::Display for TimePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let random = match self {
+            Self::Absolute { time, random } => {
+                write!(f, "{}", time.format("%Y-%m-%dT%H:%M:%S"))?;
+                random
+            }
+            Self::Recurring {
+                weekdays,
+                time,
+                random,
+            } => {
+                write!(f, "W{:03}/T{}", weekdays.bits(), format_time(time))?;
+                random
+            }
+            Self::Timer { duration, random } => {
+                write!(f, "PT{}", format_duration(duration))?;
+                random
+            }
+            Self::RecurringTimer {
+                count,
+                duration,
+                random,
+            } => {
+                match count {
+                    Some(v) => write!(f, "R{:02}", v)?,
+                    None => write!(f, "R")?,
+                }
+                write!(f, "/PT{}", format_duration(duration))?;
+                random
+            }
+            Self::Interval {
+                weekdays,
+                start,
+                end,
+            } => {
+                if let Some(v) = weekdays {
+                    write!(f, "W{:03}/", v.bits())?;
+                }
+                return write!(f, "T{}/T{}", format_time(start), format_time(end));
+            }
+            Self::Unknown(v) => return f.write_str(v),
+        };
+        match random {
+            Some(v) => write!(f, "A{}", format_duration(v)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for TimePattern {
+    type Err = ParseTimePatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s, random) = match s.find('A') {
+            Some(index) => (&s[..index], Some(parse_duration(&s[index + 1..])?)),
+            None => (s, None),
+        };
+        let without_random = |pattern: Self| match random {
+            Some(_) => Err(ParseTimePatternError::UnexpectedRandom),
+            None => Ok(pattern),
+        };
+        if let Some(s) = s.strip_prefix('W') {
+            let (bits, s) = s
+                .split_once('/')
+                .ok_or(ParseTimePatternError::InvalidFormat)?;
+            let weekdays = bits
+                .parse()
+                .ok()
+                .filter(|v| *v != 0)
+                .and_then(Weekdays::from_bits)
+                .ok_or(ParseTimePatternError::InvalidWeekdays)?;
+            let s = s
+                .strip_prefix('T')
+                .ok_or(ParseTimePatternError::InvalidFormat)?;
+            match s.split_once("/T") {
+                Some((start, end)) => without_random(Self::Interval {
+                    weekdays: Some(weekdays),
+                    start: parse_time(start)?,
+                    end: parse_time(end)?,
+                }),
+                None => Ok(Self::Recurring {
+                    weekdays,
+                    time: parse_time(s)?,
+                    random,
+                }),
+            }
+        } else if let Some(s) = s.strip_prefix("PT") {
+            Ok(Self::Timer {
+                duration: parse_duration(s)?,
+                random,
+            })
+        } else if let Some(s) = s.strip_prefix('R') {
+            let (count, s) = s
+                .split_once("/PT")
+                .ok_or(ParseTimePatternError::InvalidFormat)?;
+            let count = match count {
+                "" => None,
+                v => Some(
+                    v.parse()
+                        .map_err(|_| ParseTimePatternError::InvalidFormat)?,
+                ),
+            };
+            Ok(Self::RecurringTimer {
+                count,
+                duration: parse_duration(s)?,
+                random,
+            })
+        } else if let Some(s) = s.strip_prefix('T') {
+            let (start, end) = s
+                .split_once("/T")
+                .ok_or(ParseTimePatternError::InvalidFormat)?;
+            without_random(Self::Interval {
+                weekdays: None,
+                start: parse_time(start)?,
+                end: parse_time(end)?,
+            })
+        } else {
+            Ok(Self::Absolute {
+                time: NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S")
+                    .map_err(|_| ParseTimePatternError::InvalidTime)?,
+                random,
+            })
+        }
+    }
+}
+
+impl Serialize for TimePattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.validate().map_err(ser::Error::custom)?;
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TimePattern {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(value.parse().unwrap_or(Self::Unknown(value)))
+    }
+}
+
+/// Errors that can occur while parsing a string to a time pattern.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ParseTimePatternError {
+    /// Error that occurs when the string does not match any pattern.
+    #[error("Invalid time pattern format")]
+    InvalidFormat,
+    /// Error that occurs when a date or time is invalid.
+    #[error("Invalid time")]
+    InvalidTime,
+    /// Error that occurs when a duration is invalid.
+    #[error("Invalid duration")]
+    InvalidDuration,
+    /// Error that occurs when the weekdays are not in the range from 1 to 127.
+    #[error("Invalid weekdays")]
+    InvalidWeekdays,
+    /// Error that occurs when a time interval contains a randomization.
+    #[error("Time intervals cannot be randomized")]
+    UnexpectedRandom,
+}
+
+/// Errors that can occur while validating a time pattern.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ValidateTimePatternError {
+    /// Error that occurs when a duration is negative or longer than 99:59:59.
+    #[error("Duration is out of range")]
+    DurationOutOfRange,
+    /// Error that occurs when the weekdays do not contain any day.
+    #[error("Weekdays are empty")]
+    EmptyWeekdays,
+}
+
+/// Set of weekdays of a recurring time pattern.
+///
+/// Multiple weekdays can be combined with the `|` operator.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Weekdays(u8);
+
+impl Weekdays {
+    /// Monday.
+    pub const MONDAY: Self = Self(64);
+    /// Tuesday.
+    pub const TUESDAY: Self = Self(32);
+    /// Wednesday.
+    pub const WEDNESDAY: Self = Self(16);
+    /// Thursday.
+    pub const THURSDAY: Self = Self(8);
+    /// Friday.
+    pub const FRIDAY: Self = Self(4);
+    /// Saturday.
+    pub const SATURDAY: Self = Self(2);
+    /// Sunday.
+    pub const SUNDAY: Self = Self(1);
+    /// Monday to Friday.
+    pub const WORKDAYS: Self = Self(124);
+    /// Saturday and Sunday.
+    pub const WEEKEND: Self = Self(3);
+    /// Every day of the week.
+    pub const ALL: Self = Self(127);
+
+    /// Creates weekdays from the bitmask used by the Philips Hue API.
+    ///
+    /// Returns `None` if the value is greater than 127.
+    pub fn from_bits(bits: u8) -> Option<Self> {
+        if bits <= 127 {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bitmask used by the Philips Hue API.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns whether the given weekday is contained.
+    pub fn contains(&self, weekday: Weekday) -> bool {
+        self.0 & Self::from(weekday).0 != 0
+    }
+
+    /// Returns whether the weekday of the given date is contained.
+    pub fn contains_date<D: Datelike>(&self, date: &D) -> bool {
+        self.contains(date.weekday())
+    }
+}
+
+impl From<Weekday> for Weekdays {
+    fn from(weekday: Weekday) -> Self {
+        Self(64 >> weekday.num_days_from_monday())
+    }
+}
+
+impl BitOr for Weekdays {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
 /// Status of a schedule.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -117,7 +718,7 @@ pub enum Status {
 }
 
 /// Struct for creating a schedule.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Setters)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Creator {
     /// Sets the name of the schedule.
@@ -132,7 +733,7 @@ pub struct Creator {
     /// Sets the local time of the schedule.
     #[serde(rename = "localtime")]
     #[setters(skip)]
-    pub local_time: String,
+    pub local_time: TimePattern,
     /// Sets the status of the schedule.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Status>,
@@ -146,7 +747,7 @@ pub struct Creator {
 
 impl Creator {
     /// Creates a new [`Creator`].
-    pub fn new(command: Command, local_time: String) -> Self {
+    pub fn new(command: Command, local_time: TimePattern) -> Self {
         Self {
             name: None,
             description: None,
@@ -166,7 +767,7 @@ impl resource::Creator for Creator {
 }
 
 /// Struct for modifying attributes of a schedule.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Setters)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Modifier {
     /// Sets the name of the schedule.
@@ -180,7 +781,7 @@ pub struct Modifier {
     pub command: Option<Command>,
     /// Sets the local time of the schedule.
     #[serde(skip_serializing_if = "Option::is_none", rename = "localtime")]
-    pub local_time: Option<String>,
+    pub local_time: Option<TimePattern>,
     /// Sets the status of the schedule.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Status>,
@@ -206,6 +807,7 @@ impl resource::Modifier for Modifier {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::schedule;
     use serde_json::json;
 
     #[test]
@@ -259,6 +861,311 @@ mod tests {
             "body": {}
         });
         assert_eq!(command_json, expected_json);
+
+        let command = Command::delete(
+            resource::resourcelink::LinkKind::Schedule,
+            "2".into(),
+            "user",
+        );
+        let command_json = serde_json::to_value(command).unwrap();
+        let expected_json = json!({
+            "address": "/api/user/schedules/2",
+            "method": "DELETE",
+            "body": {}
+        });
+        assert_eq!(command_json, expected_json);
+    }
+
+    #[test]
+    fn command_username() {
+        let modifier = resource::light::StateModifier::new().with_on(true);
+        let command = Command::from_modifier(&modifier, "1".into(), "user").unwrap();
+        assert_eq!(command.username(), Some("user"));
+
+        let command = Command {
+            address: "/lights/1/state".into(),
+            request_method: CommandRequestMethod::Put,
+            body: json!({"on": true}),
+        };
+        assert_eq!(command.username(), None);
+    }
+
+    #[test]
+    fn decode_command() {
+        use resource::{light, sensor, Operation, RequestMethod};
+
+        let modifier = sensor::ConfigModifier::new().with_on(false);
+        let command = Command::from_modifier(&modifier, "3".into(), "user").unwrap();
+        let expected = Operation::SensorConfig {
+            id: "3".into(),
+            modifier,
+        };
+        assert_eq!(command.operation(), expected);
+
+        let scanner = light::Scanner::new();
+        let command = Command::from_scanner(&scanner, "user").unwrap();
+        assert_eq!(command.operation(), Operation::SearchLights(scanner));
+
+        let command = Command {
+            address: "/api/user/config".into(),
+            request_method: CommandRequestMethod::Put,
+            body: json!({"name": "bridge"}),
+        };
+        let expected = Operation::Unknown {
+            address: "/api/user/config".into(),
+            request_method: RequestMethod::Put,
+            body: json!({"name": "bridge"}),
+        };
+        assert_eq!(command.operation(), expected);
+    }
+
+    #[test]
+    fn parse_time_pattern() {
+        let time = NaiveTime::from_hms_opt(7, 0, 0).unwrap();
+        let patterns = vec![
+            (
+                "2020-01-02T07:00:00",
+                TimePattern::Absolute {
+                    time: chrono::NaiveDate::from_ymd_opt(2020, 1, 2)
+                        .unwrap()
+                        .and_time(time),
+                    random: None,
+                },
+            ),
+            (
+                "W127/T07:00:00A00:30:00",
+                TimePattern::Recurring {
+                    weekdays: Weekdays::ALL,
+                    time,
+                    random: Some(Duration::minutes(30)),
+                },
+            ),
+            (
+                "PT00:10:00",
+                TimePattern::Timer {
+                    duration: Duration::minutes(10),
+                    random: None,
+                },
+            ),
+            (
+                "R05/PT01:00:00A00:00:10",
+                TimePattern::RecurringTimer {
+                    count: Some(5),
+                    duration: Duration::hours(1),
+                    random: Some(Duration::seconds(10)),
+                },
+            ),
+            (
+                "R/PT00:00:30",
+                TimePattern::RecurringTimer {
+                    count: None,
+                    duration: Duration::seconds(30),
+                    random: None,
+                },
+            ),
+            (
+                "T07:00:00/T09:00:00",
+                TimePattern::Interval {
+                    weekdays: None,
+                    start: time,
+                    end: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                },
+            ),
+            (
+                "W003/T07:00:00/T09:00:00",
+                TimePattern::Interval {
+                    weekdays: Some(Weekdays::SATURDAY | Weekdays::SUNDAY),
+                    start: time,
+                    end: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                },
+            ),
+        ];
+        for (string, pattern) in patterns {
+            assert_eq!(string.parse::<TimePattern>(), Ok(pattern.clone()));
+            assert_eq!(pattern.to_string(), string);
+        }
+
+        assert_eq!(
+            "W128/T07:00:00".parse::<TimePattern>(),
+            Err(ParseTimePatternError::InvalidWeekdays)
+        );
+        assert_eq!(
+            "PT00:61:00".parse::<TimePattern>(),
+            Err(ParseTimePatternError::InvalidDuration)
+        );
+        assert_eq!(
+            "T07:00:00/T09:00:00A00:10:00".parse::<TimePattern>(),
+            Err(ParseTimePatternError::UnexpectedRandom)
+        );
+        assert_eq!(
+            "W000/T07:00:00".parse::<TimePattern>(),
+            Err(ParseTimePatternError::InvalidWeekdays)
+        );
+        assert!(Weekdays::WORKDAYS.contains(Weekday::Mon));
+        assert!(!Weekdays::WORKDAYS.contains(Weekday::Sun));
+    }
+
+    #[test]
+    fn validate_time_pattern() {
+        let max_duration = Duration::seconds(99 * 3600 + 59 * 60 + 59);
+        let pattern = TimePattern::RecurringTimer {
+            count: None,
+            duration: max_duration,
+            random: Some(max_duration),
+        };
+        assert_eq!(pattern.validate(), Ok(()));
+        assert_eq!(pattern.to_string(), "R/PT99:59:59A99:59:59");
+        assert_eq!(pattern.to_string().parse::<TimePattern>(), Ok(pattern));
+
+        let invalid_patterns = vec![
+            (
+                TimePattern::Timer {
+                    duration: max_duration + Duration::seconds(1),
+                    random: None,
+                },
+                ValidateTimePatternError::DurationOutOfRange,
+            ),
+            (
+                TimePattern::Timer {
+                    duration: Duration::seconds(-1),
+                    random: None,
+                },
+                ValidateTimePatternError::DurationOutOfRange,
+            ),
+            (
+                TimePattern::Absolute {
+                    time: NaiveDateTime::parse_from_str("2020-01-01T07:00:00", "%Y-%m-%dT%H:%M:%S")
+                        .unwrap(),
+                    random: Some(Duration::hours(100)),
+                },
+                ValidateTimePatternError::DurationOutOfRange,
+            ),
+            (
+                TimePattern::Recurring {
+                    weekdays: Weekdays::from_bits(0).unwrap(),
+                    time: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+                    random: None,
+                },
+                ValidateTimePatternError::EmptyWeekdays,
+            ),
+        ];
+        for (pattern, error) in invalid_patterns {
+            assert_eq!(pattern.validate(), Err(error));
+            assert!(serde_json::to_value(&pattern).is_err());
+        }
+    }
+
+    #[test]
+    fn deserialize_unknown_time_pattern() {
+        let json = json!({
+            "name": "test",
+            "description": "",
+            "command": {
+                "address": "/api/user/lights/1/state",
+                "method": "PUT",
+                "body": {"on": true}
+            },
+            "localtime": "2020-01-01T07:00:00/2020-01-02T07:00:00",
+            "status": "enabled"
+        });
+        let schedule: Schedule = serde_json::from_value(json).unwrap();
+        let pattern = TimePattern::Unknown("2020-01-01T07:00:00/2020-01-02T07:00:00".into());
+        assert_eq!(schedule.local_time, pattern);
+        assert_eq!(
+            serde_json::to_value(&pattern).unwrap(),
+            json!("2020-01-01T07:00:00/2020-01-02T07:00:00")
+        );
+        assert!("2020-01-01T07:00:00/2020-01-02T07:00:00"
+            .parse::<TimePattern>()
+            .is_err());
+    }
+
+    #[test]
+    fn compute_fire_times() {
+        // Wednesday, 2020-01-01 11:30:00 UTC, 12:30:00 local time
+        let date = chrono::NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let now = date.and_hms_opt(11, 30, 0).unwrap();
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let local = |d: u32, h: u32, m: u32| {
+            offset
+                .from_local_datetime(
+                    &chrono::NaiveDate::from_ymd_opt(2020, 1, d)
+                        .unwrap()
+                        .and_hms_opt(h, m, 0)
+                        .unwrap(),
+                )
+                .unwrap()
+        };
+        let fire_times = |schedule: &Schedule, n: usize| -> Vec<DateTime<FixedOffset>> {
+            schedule.fire_times_from(now, offset).take(n).collect()
+        };
+
+        let alarm = schedule("W124/T07:00:00A00:10:00", None);
+        assert_eq!(
+            fire_times(&alarm, 3),
+            vec![local(2, 7, 0), local(3, 7, 0), local(6, 7, 0)]
+        );
+
+        let absolute = schedule("2020-01-01T12:00:00", None);
+        assert!(fire_times(&absolute, 1).is_empty());
+        let absolute = schedule("2020-01-01T13:00:00", None);
+        assert_eq!(fire_times(&absolute, 2), vec![local(1, 13, 0)]);
+
+        let timer = schedule("PT00:10:00", Some(date.and_hms_opt(11, 25, 0).unwrap()));
+        assert_eq!(fire_times(&timer, 2), vec![local(1, 12, 35)]);
+
+        let timer = schedule("R03/PT00:10:00", Some(date.and_hms_opt(11, 15, 0).unwrap()));
+        assert_eq!(
+            fire_times(&timer, 5),
+            vec![local(1, 12, 35), local(1, 12, 45)]
+        );
+
+        let timer = schedule("R/PT00:00:01", Some(date.and_hms_opt(11, 0, 0).unwrap()));
+        let start = std::time::Instant::now();
+        assert_eq!(
+            fire_times(&timer, 1),
+            vec![local(1, 12, 30) + Duration::seconds(1)]
+        );
+        let timer = schedule(
+            "R/PT00:00:01",
+            Some(now - Duration::days(365) + Duration::milliseconds(500)),
+        );
+        assert_eq!(
+            fire_times(&timer, 1),
+            vec![local(1, 12, 30) + Duration::milliseconds(500)]
+        );
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+        let timer = schedule("R03/PT00:10:00", Some(date.and_hms_opt(10, 0, 0).unwrap()));
+        assert!(fire_times(&timer, 1).is_empty());
+
+        let mut disabled = schedule("W127/T07:00:00", None);
+        disabled.status = Status::Disabled;
+        assert!(fire_times(&disabled, 1).is_empty());
+    }
+
+    #[test]
+    fn compute_fire_times_across_dst() {
+        // Friday, 2020-03-27 12:00:00 UTC in Central European Time. Daylight saving time starts
+        // on Sunday, 2020-03-29 at 02:00:00 local time.
+        let now = chrono::NaiveDate::from_ymd_opt(2020, 3, 27)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let alarm = schedule("W127/T07:00:00", None);
+        let fire_times: Vec<_> = alarm.fire_times_from(now, offset).take(3).collect();
+        let local_times: Vec<_> = fire_times.iter().map(|v| v.naive_local()).collect();
+        let expected: Vec<_> = (28..=30)
+            .map(|d| {
+                chrono::NaiveDate::from_ymd_opt(2020, 3, d)
+                    .unwrap()
+                    .and_hms_opt(7, 0, 0)
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(local_times, expected);
+        // The offset is not changed by daylight saving time.
+        assert!(fire_times.iter().all(|v| *v.offset() == offset));
     }
 
     #[test]
@@ -269,7 +1176,8 @@ mod tests {
             body: json!({"on": true}),
         };
 
-        let creator = Creator::new(command.clone(), "2020-01-01T00:00:00".into());
+        let local_time: TimePattern = "2020-01-01T00:00:00".parse().unwrap();
+        let creator = Creator::new(command.clone(), local_time.clone());
         let creator_json = serde_json::to_value(creator).unwrap();
         let expected_json = json!({
             "command": {
@@ -287,7 +1195,7 @@ mod tests {
             name: Some("test".into()),
             description: Some("description test".into()),
             command,
-            local_time: "2020-01-01T00:00:00".into(),
+            local_time,
             status: Some(Status::Enabled),
             auto_delete: Some(false),
             recycle: Some(true),
@@ -326,7 +1234,10 @@ mod tests {
                 request_method: CommandRequestMethod::Put,
                 body: json!({"on": true}),
             }),
-            local_time: Some("2020-01-01T00:00:00".into()),
+            local_time: Some(TimePattern::Timer {
+                duration: Duration::minutes(10),
+                random: None,
+            }),
             status: Some(Status::Disabled),
             auto_delete: Some(true),
         };
@@ -341,7 +1252,7 @@ mod tests {
                     "on": true
                 }
             },
-            "localtime": "2020-01-01T00:00:00",
+            "localtime": "PT00:10:00",
             "status": "disabled",
             "autodelete": true
         });
@@ -1,8 +1,13 @@
 #![allow(clippy::needless_update)]
 
-use crate::{resource, util};
+use crate::resource::{self, Alert};
+use crate::util;
+use chrono::NaiveDateTime;
 use derive_setters::Setters;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value as JsonValue;
+use std::hash::{Hash, Hasher};
+use thiserror::Error as ThisError;
 
 /// A sensor.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
@@ -12,9 +17,6 @@ pub struct Sensor {
     pub id: String,
     /// Name of the sensor.
     pub name: String,
-    /// Type name of the sensor.
-    #[serde(rename = "type")]
-    pub type_name: String,
     /// Model identifier of the sensor.
     #[serde(rename = "modelid")]
     pub model_id: String,
@@ -33,10 +35,9 @@ pub struct Sensor {
     /// Software version of the sensor.
     #[serde(rename = "swversion")]
     pub software_version: Option<String>,
-    /// Current state of the sensor.
-    pub state: State,
-    /// Configuration of the sensor.
-    pub config: Config,
+    /// Kind of the sensor with its current state and configuration.
+    #[serde(flatten)]
+    pub kind: Kind,
     /// Whether the group is automatically deleted when not referenced anymore.
     pub recycle: Option<bool>,
 }
@@ -45,53 +46,1014 @@ impl Sensor {
     pub(crate) fn with_id(self, id: String) -> Self {
         Self { id, ..self }
     }
+
+    /// Returns the decoded button event of the sensor.
+    ///
+    /// Returns `None` if the sensor is not a switch or no button was pressed yet.
+    pub fn button_event(&self) -> Option<ButtonEvent> {
+        match &self.kind {
+            Kind::ZllSwitch { state, .. }
+            | Kind::ZgpSwitch { state, .. }
+            | Kind::ClipSwitch { state, .. } => {
+                ButtonEvent::decode(self.kind.type_name(), &self.model_id, state.button_event?)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl resource::Resource for Sensor {}
 
-/// Current state of a sensor.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
-pub struct State {
-    /// Whether the sensor is present.
+/// Kind of a sensor.
+///
+/// Every variant contains the state and the configuration of the sensor. Sensor types that are
+/// not known by this library, and sensors whose state or configuration does not match the known
+/// attributes of their type, are deserialized into the [`Unknown`] variant.
+///
+/// [`Unknown`]: Self::Unknown
+#[allow(missing_docs)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// Motion sensor (`ZLLPresence`).
+    ZllPresence {
+        state: PresenceState,
+        config: PresenceConfig,
+    },
+    /// Light level sensor of a motion sensor (`ZLLLightLevel`).
+    ZllLightLevel {
+        state: LightLevelState,
+        config: LightLevelConfig,
+    },
+    /// Temperature sensor of a motion sensor (`ZLLTemperature`).
+    ZllTemperature {
+        state: TemperatureState,
+        config: TemperatureConfig,
+    },
+    /// Dimmer switch, smart button or wall switch module (`ZLLSwitch`).
+    ZllSwitch {
+        state: SwitchState,
+        config: SwitchConfig,
+    },
+    /// Rotary dial of a tap dial switch (`ZLLRelativeRotary`).
+    ZllRelativeRotary {
+        state: RotaryState,
+        config: SwitchConfig,
+    },
+    /// Hue tap or Friends of Hue switch (`ZGPSwitch`).
+    ZgpSwitch {
+        state: SwitchState,
+        config: SwitchConfig,
+    },
+    /// Built-in daylight sensor of the bridge (`Daylight`).
+    Daylight {
+        state: DaylightState,
+        config: DaylightConfig,
+    },
+    /// Virtual presence sensor that is used for geofencing (`Geofence`).
+    Geofence {
+        state: PresenceState,
+        config: ClipConfig,
+    },
+    /// Virtual sensor with a boolean flag (`CLIPGenericFlag`).
+    ClipGenericFlag {
+        state: FlagState,
+        config: ClipConfig,
+    },
+    /// Virtual sensor with an integer status (`CLIPGenericStatus`).
+    ClipGenericStatus {
+        state: StatusState,
+        config: ClipConfig,
+    },
+    /// Virtual presence sensor (`CLIPPresence`).
+    ClipPresence {
+        state: PresenceState,
+        config: ClipConfig,
+    },
+    /// Virtual light level sensor (`CLIPLightLevel`).
+    ClipLightLevel {
+        state: LightLevelState,
+        config: ClipConfig,
+    },
+    /// Virtual temperature sensor (`CLIPTemperature`).
+    ClipTemperature {
+        state: TemperatureState,
+        config: ClipConfig,
+    },
+    /// Virtual switch (`CLIPSwitch`).
+    ClipSwitch {
+        state: SwitchState,
+        config: ClipConfig,
+    },
+    /// Virtual open/close sensor (`CLIPOpenClose`).
+    ClipOpenClose {
+        state: OpenCloseState,
+        config: ClipConfig,
+    },
+    /// Virtual humidity sensor (`CLIPHumidity`).
+    ClipHumidity {
+        state: HumidityState,
+        config: ClipConfig,
+    },
+    /// A sensor type that is not known by this library.
+    Unknown {
+        type_name: String,
+        state: JsonValue,
+        config: JsonValue,
+    },
+}
+
+macro_rules! kind_types {
+    ($macro:ident!($($args:tt)*)) => {
+        $macro! {
+            $($args)*
+            ZllPresence => "ZLLPresence",
+            ZllLightLevel => "ZLLLightLevel",
+            ZllTemperature => "ZLLTemperature",
+            ZllSwitch => "ZLLSwitch",
+            ZllRelativeRotary => "ZLLRelativeRotary",
+            ZgpSwitch => "ZGPSwitch",
+            Daylight => "Daylight",
+            Geofence => "Geofence",
+            ClipGenericFlag => "CLIPGenericFlag",
+            ClipGenericStatus => "CLIPGenericStatus",
+            ClipPresence => "CLIPPresence",
+            ClipLightLevel => "CLIPLightLevel",
+            ClipTemperature => "CLIPTemperature",
+            ClipSwitch => "CLIPSwitch",
+            ClipOpenClose => "CLIPOpenClose",
+            ClipHumidity => "CLIPHumidity",
+        }
+    };
+}
+
+impl<'de> Deserialize<'de> for Kind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct RawKind {
+            #[serde(rename = "type")]
+            type_name: String,
+            #[serde(default)]
+            state: JsonValue,
+            #[serde(default)]
+            config: JsonValue,
+        }
+        let raw = RawKind::deserialize(deserializer)?;
+        macro_rules! deserialize_kind {
+            ($($variant:ident => $name:literal,)*) => {
+                match raw.type_name.as_str() {
+                    $(
+                        $name => match (
+                            Deserialize::deserialize(&raw.state),
+                            Deserialize::deserialize(&raw.config),
+                        ) {
+                            (Ok(state), Ok(config)) => Kind::$variant { state, config },
+                            _ => Kind::Unknown {
+                                type_name: raw.type_name,
+                                state: raw.state,
+                                config: raw.config,
+                            },
+                        },
+                    )*
+                    _ => Kind::Unknown {
+                        type_name: raw.type_name,
+                        state: raw.state,
+                        config: raw.config,
+                    },
+                }
+            };
+        }
+        Ok(kind_types!(deserialize_kind!()))
+    }
+}
+
+impl Hash for Kind {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        macro_rules! hash_kind {
+            ($self:ident, $hasher:ident; $($variant:ident => $name:literal,)*) => {
+                match $self {
+                    $(Kind::$variant { state, config } => {
+                        state.hash($hasher);
+                        config.hash($hasher);
+                    })*
+                    Kind::Unknown { state, config, .. } => {
+                        hash_json(state, $hasher);
+                        hash_json(config, $hasher);
+                    }
+                }
+            };
+        }
+        self.type_name().hash(hasher);
+        kind_types!(hash_kind!(self, hasher;))
+    }
+}
+
+/// Hashes a JSON value consistently with its equality.
+///
+/// The attributes of objects are hashed in the order of their keys, because the order does not
+/// affect the equality of objects.
+fn hash_json<H: Hasher>(value: &JsonValue, hasher: &mut H) {
+    match value {
+        JsonValue::Null => 0.hash(hasher),
+        JsonValue::Bool(v) => (1, v).hash(hasher),
+        // Adding zero turns a negative zero into a positive zero, which are equal.
+        JsonValue::Number(v) => (2, v.as_f64().map(|v| (v + 0.0).to_bits())).hash(hasher),
+        JsonValue::String(v) => (3, v).hash(hasher),
+        JsonValue::Array(values) => {
+            (4, values.len()).hash(hasher);
+            values.iter().for_each(|v| hash_json(v, hasher));
+        }
+        JsonValue::Object(map) => {
+            (5, map.len()).hash(hasher);
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            for (key, value) in entries {
+                key.hash(hasher);
+                hash_json(value, hasher);
+            }
+        }
+    }
+}
+
+impl Kind {
+    /// Returns the type name of the sensor as used by the Philips Hue API.
+    pub fn type_name(&self) -> &str {
+        macro_rules! type_name {
+            ($self:ident; $($variant:ident => $name:literal,)*) => {
+                match $self {
+                    $(Kind::$variant { .. } => $name,)*
+                    Kind::Unknown { type_name, .. } => type_name,
+                }
+            };
+        }
+        kind_types!(type_name!(self;))
+    }
+
+    /// Returns when the state of the sensor was last updated.
+    pub fn last_updated(&self) -> Option<NaiveDateTime> {
+        macro_rules! last_updated {
+            ($self:ident; $($variant:ident => $name:literal,)*) => {
+                match $self {
+                    $(Kind::$variant { state, .. } => state.last_updated,)*
+                    Kind::Unknown { state, .. } => state
+                        .get("lastupdated")
+                        .and_then(|v| v.as_str())
+                        .and_then(|v| NaiveDateTime::parse_from_str(v, "%Y-%m-%dT%H:%M:%S").ok()),
+                }
+            };
+        }
+        kind_types!(last_updated!(self;))
+    }
+
+    /// Returns whether the sensor is on.
+    pub fn on(&self) -> Option<bool> {
+        macro_rules! on {
+            ($self:ident; $($variant:ident => $name:literal,)*) => {
+                match $self {
+                    $(Kind::$variant { config, .. } => Some(config.on),)*
+                    Kind::Unknown { config, .. } => config.get("on").and_then(|v| v.as_bool()),
+                }
+            };
+        }
+        kind_types!(on!(self;))
+    }
+
+    /// Returns whether the sensor can be reached by the bridge.
+    ///
+    /// Returns `None` if the sensor does not report its reachability.
+    pub fn reachable(&self) -> Option<bool> {
+        match self {
+            Self::ZllPresence { config, .. } => config.reachable,
+            Self::ZllLightLevel { config, .. } => config.reachable,
+            Self::ZllTemperature { config, .. } => config.reachable,
+            Self::ZllSwitch { config, .. }
+            | Self::ZllRelativeRotary { config, .. }
+            | Self::ZgpSwitch { config, .. } => config.reachable,
+            Self::Daylight { .. } => None,
+            Self::Geofence { config, .. }
+            | Self::ClipGenericFlag { config, .. }
+            | Self::ClipGenericStatus { config, .. }
+            | Self::ClipPresence { config, .. }
+            | Self::ClipLightLevel { config, .. }
+            | Self::ClipTemperature { config, .. }
+            | Self::ClipSwitch { config, .. }
+            | Self::ClipOpenClose { config, .. }
+            | Self::ClipHumidity { config, .. } => config.reachable,
+            Self::Unknown { config, .. } => config.get("reachable").and_then(|v| v.as_bool()),
+        }
+    }
+
+    /// Returns the current battery state.
+    ///
+    /// Returns `None` if the sensor is not battery powered.
+    pub fn battery(&self) -> Option<Battery> {
+        match self {
+            Self::ZllPresence { config, .. } => config.battery,
+            Self::ZllLightLevel { config, .. } => config.battery,
+            Self::ZllTemperature { config, .. } => config.battery,
+            Self::ZllSwitch { config, .. }
+            | Self::ZllRelativeRotary { config, .. }
+            | Self::ZgpSwitch { config, .. } => config.battery,
+            Self::Daylight { .. } => None,
+            Self::Geofence { config, .. }
+            | Self::ClipGenericFlag { config, .. }
+            | Self::ClipGenericStatus { config, .. }
+            | Self::ClipPresence { config, .. }
+            | Self::ClipLightLevel { config, .. }
+            | Self::ClipTemperature { config, .. }
+            | Self::ClipSwitch { config, .. }
+            | Self::ClipOpenClose { config, .. }
+            | Self::ClipHumidity { config, .. } => config.battery,
+            Self::Unknown { config, .. } => config
+                .get("battery")
+                .and_then(|v| v.as_u64())
+                .map(|v| Battery::from_percent(v.min(100) as u8)),
+        }
+    }
+
+    /// Returns the current temperature of a temperature sensor.
+    pub fn temperature(&self) -> Option<Temperature> {
+        match self {
+            Self::ZllTemperature { state, .. } | Self::ClipTemperature { state, .. } => {
+                state.temperature
+            }
+            Self::Unknown { state, .. } => state
+                .get("temperature")
+                .and_then(|v| v.as_i64())
+                .map(|v| Temperature::from_raw(v as i32)),
+            _ => None,
+        }
+    }
+
+    /// Returns the current light level of a light level sensor.
+    pub fn light_level(&self) -> Option<LightLevel> {
+        match self {
+            Self::ZllLightLevel { state, .. } | Self::ClipLightLevel { state, .. } => {
+                state.light_level
+            }
+            Self::Unknown { state, .. } => state
+                .get("lightlevel")
+                .and_then(|v| v.as_u64())
+                .map(|v| LightLevel::from_raw(v as u32)),
+            _ => None,
+        }
+    }
+
+    /// Returns the state and configuration of the sensor as JSON values.
+    pub(crate) fn to_json(&self) -> (JsonValue, JsonValue) {
+        macro_rules! to_json {
+            ($self:ident; $($variant:ident => $name:literal,)*) => {
+                match $self {
+                    $(
+                        Kind::$variant { state, config } => (
+                            serde_json::to_value(state).unwrap_or_default(),
+                            serde_json::to_value(config).unwrap_or_default(),
+                        ),
+                    )*
+                    Kind::Unknown { state, config, .. } => (state.clone(), config.clone()),
+                }
+            };
+        }
+        kind_types!(to_json!(self;))
+    }
+
+    /// Returns a copy of this kind where the state is replaced by an empty state.
+    pub(crate) fn without_state(&self) -> Self {
+        macro_rules! without_state {
+            ($self:ident; $($variant:ident => $name:literal,)*) => {
+                match $self.clone() {
+                    $(
+                        Kind::$variant { config, .. } => Kind::$variant {
+                            state: Default::default(),
+                            config,
+                        },
+                    )*
+                    Kind::Unknown { type_name, config, .. } => Kind::Unknown {
+                        type_name,
+                        state: JsonValue::Null,
+                        config,
+                    },
+                }
+            };
+        }
+        kind_types!(without_state!(self;))
+    }
+}
+
+/// Temperature as reported by a temperature sensor.
+///
+/// The bridge uses hundredths of a degree Celsius.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Temperature(i32);
+
+impl Temperature {
+    /// Creates a new temperature from hundredths of a degree Celsius.
+    pub fn from_raw(value: i32) -> Self {
+        Self(value)
+    }
+
+    /// Creates a new temperature from degrees Celsius.
+    pub fn from_celsius(value: f32) -> Self {
+        Self((value * 100.0).round() as i32)
+    }
+
+    /// Creates a new temperature from degrees Fahrenheit.
+    pub fn from_fahrenheit(value: f32) -> Self {
+        Self::from_celsius((value - 32.0) * 5.0 / 9.0)
+    }
+
+    /// Returns the temperature in hundredths of a degree Celsius.
+    pub fn raw(&self) -> i32 {
+        self.0
+    }
+
+    /// Returns the temperature in degrees Celsius.
+    pub fn celsius(&self) -> f32 {
+        self.0 as f32 / 100.0
+    }
+
+    /// Returns the temperature in degrees Fahrenheit.
+    pub fn fahrenheit(&self) -> f32 {
+        self.celsius() * 9.0 / 5.0 + 32.0
+    }
+}
+
+/// Light level as reported by a light level sensor.
+///
+/// The bridge uses a logarithmic scale of `10000 * log10(lux) + 1`. The same scale is used for the
+/// dark threshold of a light level sensor.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct LightLevel(u32);
+
+impl LightLevel {
+    /// Creates a new light level from the value used by the bridge.
+    pub fn from_raw(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// Creates a new light level from an illuminance in lux.
+    ///
+    /// Illuminances below 1 lux are mapped to a light level of 0.
+    pub fn from_lux(value: f64) -> Self {
+        if value < 1.0 {
+            return Self(0);
+        }
+        Self((10000.0 * value.log10() + 1.0).round().min(u32::MAX as f64) as u32)
+    }
+
+    /// Returns the value used by the bridge.
+    pub fn raw(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the illuminance in lux.
+    pub fn lux(&self) -> f64 {
+        if self.0 == 0 {
+            return 0.0;
+        }
+        10f64.powf((self.0 as f64 - 1.0) / 10000.0)
+    }
+}
+
+/// Battery state of a sensor in percent.
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Battery(u8);
+
+impl Battery {
+    /// Creates a new battery state from a percentage.
+    ///
+    /// Values above 100 are clamped.
+    pub fn from_percent(value: u8) -> Self {
+        Self(value.min(100))
+    }
+
+    /// Returns the battery state in percent.
+    pub fn percent(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns the battery state as a fraction between 0 and 1.
+    pub fn fraction(&self) -> f32 {
+        self.0 as f32 / 100.0
+    }
+}
+
+/// Geographic coordinate in degrees, as used for the location of a daylight sensor.
+///
+/// The bridge accepts coordinates with four decimal places, so they are stored in
+/// ten-thousandths of a degree.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Coordinate(i32);
+
+impl Coordinate {
+    /// Creates a new coordinate from degrees.
+    ///
+    /// The value is rounded to four decimal places.
+    pub fn from_degrees(value: f64) -> Self {
+        Self((value * 10000.0).round() as i32)
+    }
+
+    /// Returns the coordinate in degrees.
+    pub fn degrees(&self) -> f64 {
+        self.0 as f64 / 10000.0
+    }
+}
+
+/// State of a presence sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PresenceState {
+    /// Whether presence is detected.
     pub presence: Option<bool>,
-    /// Flag of the sensor.
-    pub flag: Option<bool>,
-    /// The current battery state in percent.
+    /// When the state was last updated.
     #[serde(
+        default,
         rename = "lastupdated",
         deserialize_with = "util::deserialize_option_date_time"
     )]
-    pub last_updated: Option<chrono::NaiveDateTime>,
-    /// Button id that was pressed last.
-    #[serde(rename = "buttonevent")]
-    pub button_event: Option<u32>,
-    /// The temperature in centigrades.
-    pub temperature: Option<i32>,
-    /// The light level in centiluxes.
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// State of a light level sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct LightLevelState {
+    /// The light level.
     #[serde(rename = "lightlevel")]
-    pub light_level: Option<u32>,
-    /// Whether it's dark according to the sensor's sensitivity.
+    pub light_level: Option<LightLevel>,
+    /// Whether the light level is below the dark threshold.
     pub dark: Option<bool>,
-    /// Whether it's daytime according to the sensor's sensitivity.
+    /// Whether the light level is above the dark threshold plus the offset.
+    pub daylight: Option<bool>,
+    /// When the state was last updated.
+    #[serde(
+        default,
+        rename = "lastupdated",
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// State of a temperature sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TemperatureState {
+    /// The temperature.
+    pub temperature: Option<Temperature>,
+    /// When the state was last updated.
+    #[serde(
+        default,
+        rename = "lastupdated",
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// State of a switch.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct SwitchState {
+    /// Raw code of the last button event.
+    ///
+    /// Use [`Sensor::button_event`] to decode the code.
+    #[serde(rename = "buttonevent")]
+    pub button_event: Option<u32>,
+    /// Duration of the last button event in multiples of 100ms.
+    #[serde(rename = "eventduration")]
+    pub event_duration: Option<u16>,
+    /// When the state was last updated.
+    #[serde(
+        default,
+        rename = "lastupdated",
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// State of a rotary dial.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct RotaryState {
+    /// Raw code of the last rotary event.
+    #[serde(rename = "rotaryevent")]
+    pub rotary_event: Option<u8>,
+    /// Expected rotation of the dial.
+    #[serde(rename = "expectedrotation")]
+    pub expected_rotation: Option<i32>,
+    /// Expected duration of the rotation in milliseconds.
+    #[serde(rename = "expectedeventduration")]
+    pub expected_event_duration: Option<u32>,
+    /// When the state was last updated.
+    #[serde(
+        default,
+        rename = "lastupdated",
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// State of the daylight sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct DaylightState {
+    /// Whether it is daytime.
+    ///
+    /// This is `None` if the location of the sensor is not configured.
     pub daylight: Option<bool>,
-    // TODO: Add missing attributes (https://github.com/yuqio/huelib-rs/issues/2)
+    /// When the state was last updated.
+    #[serde(
+        default,
+        rename = "lastupdated",
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// State of a generic flag sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct FlagState {
+    /// Flag of the sensor.
+    pub flag: Option<bool>,
+    /// When the state was last updated.
+    #[serde(
+        default,
+        rename = "lastupdated",
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// State of a generic status sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct StatusState {
+    /// Status of the sensor.
+    pub status: Option<i32>,
+    /// When the state was last updated.
+    #[serde(
+        default,
+        rename = "lastupdated",
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// State of an open/close sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct OpenCloseState {
+    /// Whether the sensor is open.
+    pub open: Option<bool>,
+    /// When the state was last updated.
+    #[serde(
+        default,
+        rename = "lastupdated",
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// State of a humidity sensor.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct HumidityState {
+    /// The relative humidity in hundredths of a percent.
+    pub humidity: Option<u16>,
+    /// When the state was last updated.
+    #[serde(
+        default,
+        rename = "lastupdated",
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
+    pub last_updated: Option<NaiveDateTime>,
+}
+
+/// Configuration of a motion sensor.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PresenceConfig {
+    /// Whether the sensor is on.
+    pub on: bool,
+    /// Whether the sensor can be reached by the bridge.
+    pub reachable: Option<bool>,
+    /// The current battery state.
+    pub battery: Option<Battery>,
+    /// Alert effect of the sensor.
+    pub alert: Option<Alert>,
+    /// Whether the LED blinks when motion is detected.
+    #[serde(rename = "ledindication")]
+    pub led_indication: Option<bool>,
+    /// Whether the sensor is in test mode.
+    #[serde(rename = "usertest")]
+    pub user_test: Option<bool>,
+    /// Sensitivity of the motion detection.
+    pub sensitivity: Option<u8>,
+    /// Maximum value of the sensitivity.
+    #[serde(rename = "sensitivitymax")]
+    pub sensitivity_max: Option<u8>,
+    /// Configuration attributes that are not yet applied to the sensor.
+    pub pending: Option<Vec<String>>,
+}
+
+/// Configuration of a light level sensor.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct LightLevelConfig {
+    /// Whether the sensor is on.
+    pub on: bool,
+    /// Whether the sensor can be reached by the bridge.
+    pub reachable: Option<bool>,
+    /// The current battery state.
+    pub battery: Option<Battery>,
+    /// Alert effect of the sensor.
+    pub alert: Option<Alert>,
+    /// Threshold below which it is dark.
+    ///
+    /// The bridge accepts values up to 65535, higher values are clamped when serializing.
+    #[serde(rename = "tholddark", serialize_with = "serialize_threshold_dark")]
+    pub threshold_dark: Option<LightLevel>,
+    /// Offset to the dark threshold above which it is daylight.
+    #[serde(rename = "tholdoffset")]
+    pub threshold_offset: Option<u16>,
+    /// Whether the LED blinks when the state changes.
+    #[serde(rename = "ledindication")]
+    pub led_indication: Option<bool>,
+    /// Whether the sensor is in test mode.
+    #[serde(rename = "usertest")]
+    pub user_test: Option<bool>,
+    /// Configuration attributes that are not yet applied to the sensor.
+    pub pending: Option<Vec<String>>,
+}
+
+/// Configuration of a temperature sensor.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TemperatureConfig {
+    /// Whether the sensor is on.
+    pub on: bool,
+    /// Whether the sensor can be reached by the bridge.
+    pub reachable: Option<bool>,
+    /// The current battery state.
+    pub battery: Option<Battery>,
+    /// Alert effect of the sensor.
+    pub alert: Option<Alert>,
+    /// Whether the LED blinks when the state changes.
+    #[serde(rename = "ledindication")]
+    pub led_indication: Option<bool>,
+    /// Whether the sensor is in test mode.
+    #[serde(rename = "usertest")]
+    pub user_test: Option<bool>,
+    /// Configuration attributes that are not yet applied to the sensor.
+    pub pending: Option<Vec<String>>,
+}
+
+/// Configuration of a switch.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct SwitchConfig {
+    /// Whether the sensor is on.
+    pub on: bool,
+    /// Whether the sensor can be reached by the bridge.
+    pub reachable: Option<bool>,
+    /// The current battery state.
+    ///
+    /// Not present for switches without a battery.
+    pub battery: Option<Battery>,
+    /// Configuration attributes that are not yet applied to the sensor.
+    pub pending: Option<Vec<String>>,
+}
+
+/// Configuration of the daylight sensor.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct DaylightConfig {
+    /// Whether the sensor is on.
+    pub on: bool,
+    /// Whether the location of the sensor is configured.
+    pub configured: Option<bool>,
+    /// Offset in minutes that is added to the time of the sunrise.
+    #[serde(rename = "sunriseoffset")]
+    pub sunrise_offset: Option<i8>,
+    /// Offset in minutes that is added to the time of the sunset.
+    #[serde(rename = "sunsetoffset")]
+    pub sunset_offset: Option<i8>,
+    /// Configuration attributes that are not yet applied to the sensor.
+    pub pending: Option<Vec<String>>,
 }
 
-/// Configuration of a sensor.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
-pub struct Config {
+/// Configuration of a CLIP sensor.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ClipConfig {
     /// Whether the sensor is on.
     pub on: bool,
     /// Whether the sensor can be reached by the bridge.
     pub reachable: Option<bool>,
-    /// The current battery state in percent.
+    /// The current battery state.
+    ///
+    /// Not present when not provided on creation.
+    pub battery: Option<Battery>,
+    /// URL of the device that is represented by the sensor.
+    pub url: Option<String>,
+}
+
+/// Decoded button event of a switch sensor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ButtonEvent {
+    /// Number of the button, starting at 1.
+    pub button: u8,
+    /// Action that was performed with the button.
+    pub action: ButtonAction,
+}
+
+impl ButtonEvent {
+    /// Decodes the raw `buttonevent` attribute of a sensor state.
+    ///
+    /// The encoding depends on the type and the model of the sensor:
+    ///
+    /// - `ZLLSwitch` (e.g. dimmer switches and smart buttons): The thousands digit is the button
+    ///   and the last digit is the action.
+    /// - `ZGPSwitch` with a model identifier beginning with `FOH` (Friends of Hue switches): The
+    ///   codes 16 to 19 are presses and 20 to 23 are releases of the four buttons. Pressing both
+    ///   upper or both lower buttons is reported as button 5 or 6.
+    /// - `ZGPSwitch` (Hue tap): The codes 34, 16, 17 and 18 are the buttons 1 to 4. Since only a
+    ///   single event is reported per press, these are decoded as [`ButtonAction::ShortRelease`].
     ///
-    /// Only for battery powered devices. Not present when not provided on creation (CLIP sensors).
-    pub battery: Option<u8>,
+    /// Returns `None` if the type is not a switch or the code is unknown.
+    pub fn decode(type_name: &str, model_id: &str, code: u32) -> Option<Self> {
+        let (button, action) = match type_name {
+            "ZGPSwitch" if model_id.starts_with("FOH") => match code {
+                16..=19 => (code - 15, ButtonAction::InitialPress),
+                20..=23 => (code - 19, ButtonAction::ShortRelease),
+                100 => (5, ButtonAction::InitialPress),
+                101 => (5, ButtonAction::ShortRelease),
+                98 => (6, ButtonAction::InitialPress),
+                99 => (6, ButtonAction::ShortRelease),
+                _ => return None,
+            },
+            "ZGPSwitch" => match code {
+                34 => (1, ButtonAction::ShortRelease),
+                16 => (2, ButtonAction::ShortRelease),
+                17 => (3, ButtonAction::ShortRelease),
+                18 => (4, ButtonAction::ShortRelease),
+                _ => return None,
+            },
+            "ZLLSwitch" | "CLIPSwitch" => {
+                let action = match code % 1000 {
+                    0 => ButtonAction::InitialPress,
+                    1 => ButtonAction::Repeat,
+                    2 => ButtonAction::ShortRelease,
+                    3 => ButtonAction::LongRelease,
+                    _ => return None,
+                };
+                (code / 1000, action)
+            }
+            _ => return None,
+        };
+        Some(Self {
+            button: button as u8,
+            action,
+        })
+    }
+}
+
+/// Action that was performed with a button.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ButtonAction {
+    /// The button was pressed.
+    InitialPress,
+    /// The button is held down.
+    Repeat,
+    /// The button was released after a short press.
+    ShortRelease,
+    /// The button was released after being held down.
+    LongRelease,
+}
+
+/// Kind of a sensor that can be created.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum CreatableKind {
+    /// Virtual sensor with a boolean flag.
+    #[serde(rename = "CLIPGenericFlag")]
+    ClipGenericFlag,
+    /// Virtual sensor with an integer status.
+    #[serde(rename = "CLIPGenericStatus")]
+    ClipGenericStatus,
+    /// Virtual presence sensor.
+    #[serde(rename = "CLIPPresence")]
+    ClipPresence,
+    /// Virtual light level sensor.
+    #[serde(rename = "CLIPLightLevel")]
+    ClipLightLevel,
+    /// Virtual temperature sensor.
+    #[serde(rename = "CLIPTemperature")]
+    ClipTemperature,
+    /// Virtual switch.
+    #[serde(rename = "CLIPSwitch")]
+    ClipSwitch,
+    /// Virtual open/close sensor.
+    #[serde(rename = "CLIPOpenClose")]
+    ClipOpenClose,
+    /// Virtual humidity sensor.
+    #[serde(rename = "CLIPHumidity")]
+    ClipHumidity,
+    /// Virtual presence sensor that is used for geofencing.
+    Geofence,
+}
+
+impl CreatableKind {
+    /// Returns the type name of the sensor as used by the Philips Hue API.
+    pub fn type_name(&self) -> &str {
+        match self {
+            Self::ClipGenericFlag => "CLIPGenericFlag",
+            Self::ClipGenericStatus => "CLIPGenericStatus",
+            Self::ClipPresence => "CLIPPresence",
+            Self::ClipLightLevel => "CLIPLightLevel",
+            Self::ClipTemperature => "CLIPTemperature",
+            Self::ClipSwitch => "CLIPSwitch",
+            Self::ClipOpenClose => "CLIPOpenClose",
+            Self::ClipHumidity => "CLIPHumidity",
+            Self::Geofence => "Geofence",
+        }
+    }
+}
+
+/// Struct for creating a sensor.
+///
+/// Only CLIP sensors can be created, the bridge rejects all other sensor types.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct Creator {
+    /// Sets the name of the sensor.
+    #[setters(skip)]
+    pub name: String,
+    /// Sets the kind of the sensor.
+    #[serde(rename = "type")]
+    #[setters(skip)]
+    pub kind: CreatableKind,
+    /// Sets the model identifier of the sensor.
+    #[serde(rename = "modelid")]
+    #[setters(skip)]
+    pub model_id: String,
+    /// Sets the software version of the sensor.
+    #[serde(rename = "swversion")]
+    #[setters(skip)]
+    pub software_version: String,
+    /// Sets the unique identifier of the sensor.
+    #[serde(rename = "uniqueid")]
+    #[setters(skip)]
+    pub unique_id: String,
+    /// Sets the manufacturer name of the sensor.
+    #[serde(rename = "manufacturername")]
+    #[setters(skip)]
+    pub manufacturer_name: String,
+    /// Sets the initial state of the sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<StateModifier>,
+    /// Sets the initial configuration of the sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<ConfigModifier>,
+    /// Sets whether the sensor is automatically deleted when not referenced anymore.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recycle: Option<bool>,
+}
+
+impl Creator {
+    /// Creates a new [`Creator`].
+    pub fn new(
+        name: String,
+        kind: CreatableKind,
+        model_id: String,
+        software_version: String,
+        unique_id: String,
+        manufacturer_name: String,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            model_id,
+            software_version,
+            unique_id,
+            manufacturer_name,
+            state: None,
+            config: None,
+            recycle: None,
+        }
+    }
+
+    /// Checks whether the initial state and configuration are supported by the sensor kind.
+    pub fn validate(&self) -> Result<(), ValidateError> {
+        if let Some(state) = &self.state {
+            state.validate(self.kind.type_name())?;
+        }
+        if let Some(config) = &self.config {
+            config.validate(self.kind.type_name())?;
+        }
+        Ok(())
+    }
+}
+
+impl resource::Creator for Creator {
+    fn url_suffix() -> String {
+        "sensors".to_owned()
+    }
 }
 
 /// Modifier for sensor attributes.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct AttributeModifier {
     /// Sets the name of the sensor.
@@ -114,12 +1076,44 @@ impl resource::Modifier for AttributeModifier {
 }
 
 /// Modifier for the sensor state.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+///
+/// Only the state of CLIP sensors can be modified. Use [`validate`] to check whether the
+/// attributes are supported by a sensor type.
+///
+/// [`validate`]: Self::validate
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct StateModifier {
     /// Sets the presence of the sensor.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub presence: Option<bool>,
+    /// Sets the flag of the sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flag: Option<bool>,
+    /// Sets the status of the sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+    /// Sets whether the sensor is open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open: Option<bool>,
+    /// Sets the relative humidity in hundredths of a percent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub humidity: Option<u16>,
+    /// Sets the temperature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<Temperature>,
+    /// Sets the light level.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "lightlevel")]
+    pub light_level: Option<LightLevel>,
+    /// Sets whether it is dark.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dark: Option<bool>,
+    /// Sets whether it is daylight.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daylight: Option<bool>,
+    /// Sets the raw code of the button event.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "buttonevent")]
+    pub button_event: Option<u32>,
 }
 
 impl StateModifier {
@@ -127,6 +1121,26 @@ impl StateModifier {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Checks whether all attributes of the modifier can be set on a sensor of the given type.
+    ///
+    /// Sensor types that are not known by this library are not checked.
+    pub fn validate(&self, type_name: &str) -> Result<(), ValidateError> {
+        let supported: &[&str] = match type_name {
+            "CLIPGenericFlag" => &["flag"],
+            "CLIPGenericStatus" => &["status"],
+            "CLIPPresence" | "Geofence" => &["presence"],
+            "CLIPOpenClose" => &["open"],
+            "CLIPHumidity" => &["humidity"],
+            "CLIPTemperature" => &["temperature"],
+            "CLIPLightLevel" => &["lightlevel", "dark", "daylight"],
+            "CLIPSwitch" => &["buttonevent"],
+            "ZLLPresence" | "ZLLLightLevel" | "ZLLTemperature" | "ZLLSwitch"
+            | "ZLLRelativeRotary" | "ZGPSwitch" | "Daylight" => &[],
+            _ => return Ok(()),
+        };
+        validate_attributes(self, type_name, supported)
+    }
 }
 
 impl resource::Modifier for StateModifier {
@@ -137,12 +1151,143 @@ impl resource::Modifier for StateModifier {
 }
 
 /// Modifier for the sensor configuration.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+///
+/// Use [`validate`] to check whether the attributes are supported by a sensor type.
+///
+/// [`validate`]: Self::validate
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct ConfigModifier {
     /// Sets whether the sensor is on.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on: Option<bool>,
+    /// Sets whether a CLIP sensor can be reached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reachable: Option<bool>,
+    /// Sets the battery state of a CLIP sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub battery: Option<Battery>,
+    /// Sets the URL of a CLIP sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Sets the alert effect of the sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alert: Option<Alert>,
+    /// Sets whether the LED blinks when the state changes.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "ledindication")]
+    pub led_indication: Option<bool>,
+    /// Sets whether the sensor is in test mode.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "usertest")]
+    pub user_test: Option<bool>,
+    /// Sets the sensitivity of a motion sensor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<u8>,
+    /// Sets the threshold below which it is dark.
+    ///
+    /// The bridge accepts values up to 65535, higher values are clamped when serializing.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "tholddark",
+        serialize_with = "serialize_threshold_dark"
+    )]
+    pub threshold_dark: Option<LightLevel>,
+    /// Sets the offset to the dark threshold above which it is daylight.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "tholdoffset")]
+    pub threshold_offset: Option<u16>,
+    /// Sets the latitude of the daylight sensor in degrees.
+    ///
+    /// Positive values are north and negative values are south of the equator.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "lat",
+        serialize_with = "serialize_latitude",
+        deserialize_with = "deserialize_coordinate"
+    )]
+    pub latitude: Option<Coordinate>,
+    /// Sets the longitude of the daylight sensor in degrees.
+    ///
+    /// Positive values are east and negative values are west of the prime meridian.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        rename = "long",
+        serialize_with = "serialize_longitude",
+        deserialize_with = "deserialize_coordinate"
+    )]
+    pub longitude: Option<Coordinate>,
+    /// Sets the offset in minutes that is added to the time of the sunrise.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "sunriseoffset")]
+    pub sunrise_offset: Option<i8>,
+    /// Sets the offset in minutes that is added to the time of the sunset.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "sunsetoffset")]
+    pub sunset_offset: Option<i8>,
+}
+
+fn serialize_coordinate<S: Serializer>(
+    value: Coordinate,
+    positive: char,
+    negative: char,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let direction = if value.0 < 0 { negative } else { positive };
+    let value = value.0.unsigned_abs();
+    serializer.serialize_str(&format!(
+        "{:03}.{:04}{}",
+        value / 10000,
+        value % 10000,
+        direction
+    ))
+}
+
+fn serialize_threshold_dark<S: Serializer>(
+    value: &Option<LightLevel>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(v) => serializer.serialize_u16(v.raw().min(u16::MAX as u32) as u16),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn serialize_latitude<S: Serializer>(
+    value: &Option<Coordinate>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(v) => serialize_coordinate(*v, 'N', 'S', serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn serialize_longitude<S: Serializer>(
+    value: &Option<Coordinate>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(v) => serialize_coordinate(*v, 'E', 'W', serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_coordinate<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Coordinate>, D::Error> {
+    let value: Option<String> = Deserialize::deserialize(deserializer)?;
+    let value = match value {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+    let invalid = || D::Error::custom(format!("invalid coordinate '{}'", value));
+    let direction = value.chars().last().ok_or_else(invalid)?;
+    let number: f64 = value[..value.len() - direction.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    match direction {
+        'N' | 'E' => Ok(Some(Coordinate::from_degrees(number))),
+        'S' | 'W' => Ok(Some(Coordinate::from_degrees(-number))),
+        _ => Err(invalid()),
+    }
 }
 
 impl ConfigModifier {
@@ -150,6 +1295,32 @@ impl ConfigModifier {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Checks whether all attributes of the modifier can be set on a sensor of the given type.
+    ///
+    /// Sensor types that are not known by this library are not checked.
+    pub fn validate(&self, type_name: &str) -> Result<(), ValidateError> {
+        let supported: &[&str] = match type_name {
+            "ZLLPresence" => &["on", "alert", "ledindication", "usertest", "sensitivity"],
+            "ZLLLightLevel" => &[
+                "on",
+                "alert",
+                "ledindication",
+                "usertest",
+                "tholddark",
+                "tholdoffset",
+            ],
+            "ZLLTemperature" => &["on", "alert", "ledindication", "usertest"],
+            "ZLLSwitch" | "ZLLRelativeRotary" | "ZGPSwitch" => &["on"],
+            "Daylight" => &["on", "lat", "long", "sunriseoffset", "sunsetoffset"],
+            "CLIPGenericFlag" | "CLIPGenericStatus" | "CLIPPresence" | "CLIPLightLevel"
+            | "CLIPTemperature" | "CLIPSwitch" | "CLIPOpenClose" | "CLIPHumidity" | "Geofence" => {
+                &["on", "reachable", "battery", "url"]
+            }
+            _ => return Ok(()),
+        };
+        validate_attributes(self, type_name, supported)
+    }
 }
 
 impl resource::Modifier for ConfigModifier {
@@ -159,8 +1330,51 @@ impl resource::Modifier for ConfigModifier {
     }
 }
 
+fn validate_attributes<T: Serialize>(
+    modifier: &T,
+    type_name: &str,
+    supported: &[&str],
+) -> Result<(), ValidateError> {
+    let map = match serde_json::to_value(modifier) {
+        Ok(JsonValue::Object(v)) => v,
+        Ok(_) => return Err(ValidateError::Serialize("Expected an object".to_owned())),
+        Err(e) => return Err(ValidateError::Serialize(e.to_string())),
+    };
+    for attribute in map.keys() {
+        if !supported.contains(&attribute.as_str()) {
+            return Err(UnsupportedAttributeError {
+                attribute: attribute.clone(),
+                type_name: type_name.to_owned(),
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Errors that can occur while validating a sensor creator or modifier.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+pub enum ValidateError {
+    /// Error that occurs when an attribute is not supported by the sensor type.
+    #[error(transparent)]
+    UnsupportedAttribute(#[from] UnsupportedAttributeError),
+    /// Error that occurs when the attributes cannot be serialized.
+    #[error("Failed to serialize the attributes: {0}")]
+    Serialize(String),
+}
+
+/// Error that occurs when a modifier contains an attribute that is not supported by a sensor type.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[error("Attribute `{attribute}` is not supported by sensors of type `{type_name}`")]
+pub struct UnsupportedAttributeError {
+    /// Name of the attribute as used by the Philips Hue API.
+    pub attribute: String,
+    /// Type name of the sensor.
+    pub type_name: String,
+}
+
 /// Scanner for new lights.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Scanner {
     /// The device identifiers.
@@ -186,6 +1400,156 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn deserialize_sensor() {
+        let json = json!({
+            "name": "Hallway sensor",
+            "type": "ZLLPresence",
+            "modelid": "SML001",
+            "state": {"presence": true, "lastupdated": "2020-01-01T00:00:00"},
+            "config": {
+                "on": true,
+                "battery": 90,
+                "reachable": true,
+                "alert": "none",
+                "ledindication": false,
+                "usertest": false,
+                "sensitivity": 2,
+                "sensitivitymax": 2,
+                "pending": []
+            }
+        });
+        let sensor: Sensor = serde_json::from_value(json).unwrap();
+        assert_eq!(sensor.kind.type_name(), "ZLLPresence");
+        assert_eq!(sensor.kind.battery(), Some(Battery::from_percent(90)));
+        match sensor.kind {
+            Kind::ZllPresence { state, config } => {
+                assert_eq!(state.presence, Some(true));
+                assert_eq!(config.sensitivity, Some(2));
+            }
+            _ => panic!("expected ZLLPresence sensor"),
+        }
+
+        let json = json!({
+            "name": "Custom sensor",
+            "type": "CLIPCustom",
+            "modelid": "custom",
+            "state": {"value": 1, "lastupdated": "none"},
+            "config": {"on": true}
+        });
+        let sensor: Sensor = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            sensor.kind,
+            Kind::Unknown {
+                type_name: "CLIPCustom".into(),
+                state: json!({"value": 1, "lastupdated": "none"}),
+                config: json!({"on": true}),
+            }
+        );
+        assert_eq!(sensor.kind.on(), Some(true));
+        assert_eq!(sensor.kind.last_updated(), None);
+
+        let json = json!({
+            "name": "Hallway sensor",
+            "type": "ZLLPresence",
+            "modelid": "SML001",
+            "state": {"presence": "yes", "lastupdated": "2020-01-01T00:00:00"},
+            "config": {"on": true}
+        });
+        let sensor: Sensor = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            sensor.kind,
+            Kind::Unknown {
+                type_name: "ZLLPresence".into(),
+                state: json!({"presence": "yes", "lastupdated": "2020-01-01T00:00:00"}),
+                config: json!({"on": true}),
+            }
+        );
+        let sensors: std::collections::HashSet<_> =
+            vec![sensor.clone(), sensor].into_iter().collect();
+        assert_eq!(sensors.len(), 1);
+    }
+
+    #[test]
+    fn decode_button_event() {
+        let event = ButtonEvent::decode("ZLLSwitch", "RWL021", 4003).unwrap();
+        assert_eq!(
+            event,
+            ButtonEvent {
+                button: 4,
+                action: ButtonAction::LongRelease
+            }
+        );
+
+        let event = ButtonEvent::decode("ZGPSwitch", "ZGPSWITCH", 34).unwrap();
+        assert_eq!(
+            event,
+            ButtonEvent {
+                button: 1,
+                action: ButtonAction::ShortRelease
+            }
+        );
+
+        let event = ButtonEvent::decode("ZGPSwitch", "FOHSWITCH", 17).unwrap();
+        assert_eq!(
+            event,
+            ButtonEvent {
+                button: 2,
+                action: ButtonAction::InitialPress
+            }
+        );
+
+        assert_eq!(ButtonEvent::decode("ZLLSwitch", "RWL021", 1004), None);
+        assert_eq!(ButtonEvent::decode("ZLLPresence", "SML001", 1000), None);
+    }
+
+    #[test]
+    fn serialize_creator() {
+        let creator = Creator::new(
+            "test".into(),
+            CreatableKind::ClipGenericStatus,
+            "model".into(),
+            "1.0".into(),
+            "unique".into(),
+            "manufacturer".into(),
+        );
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "test",
+            "type": "CLIPGenericStatus",
+            "modelid": "model",
+            "swversion": "1.0",
+            "uniqueid": "unique",
+            "manufacturername": "manufacturer"
+        });
+        assert_eq!(creator_json, expected_json);
+
+        let creator = Creator {
+            name: "test".into(),
+            kind: CreatableKind::ClipPresence,
+            model_id: "model".into(),
+            software_version: "1.0".into(),
+            unique_id: "unique".into(),
+            manufacturer_name: "manufacturer".into(),
+            state: Some(StateModifier::new().with_presence(false)),
+            config: Some(ConfigModifier::new().with_on(true)),
+            recycle: Some(true),
+        };
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "test",
+            "type": "CLIPPresence",
+            "modelid": "model",
+            "swversion": "1.0",
+            "uniqueid": "unique",
+            "manufacturername": "manufacturer",
+            "state": {"presence": false},
+            "config": {"on": true},
+            "recycle": true
+        });
+        assert_eq!(creator_json, expected_json);
+    }
+
     #[test]
     fn serialize_attribute_modifier() {
         let modifier = AttributeModifier::new();
@@ -210,12 +1574,67 @@ mod tests {
 
         let modifier = StateModifier {
             presence: Some(true),
+            flag: Some(false),
+            status: Some(2),
+            open: Some(true),
+            humidity: Some(4000),
+            temperature: Some(Temperature::from_raw(2150)),
+            light_level: Some(LightLevel::from_raw(10000)),
+            dark: Some(true),
+            daylight: Some(false),
+            button_event: Some(1002),
         };
         let modifier_json = serde_json::to_value(modifier).unwrap();
-        let expected_json = json!({"presence": true});
+        let expected_json = json!({
+            "presence": true,
+            "flag": false,
+            "status": 2,
+            "open": true,
+            "humidity": 4000,
+            "temperature": 2150,
+            "lightlevel": 10000,
+            "dark": true,
+            "daylight": false,
+            "buttonevent": 1002
+        });
         assert_eq!(modifier_json, expected_json);
     }
 
+    #[test]
+    fn convert_units() {
+        let temperature = Temperature::from_raw(2150);
+        assert_eq!(temperature.celsius(), 21.5);
+        assert!((temperature.fahrenheit() - 70.7).abs() < 0.001);
+        assert_eq!(Temperature::from_celsius(21.5), temperature);
+        assert_eq!(Temperature::from_fahrenheit(70.7), temperature);
+        assert_eq!(Temperature::from_celsius(-4.25).raw(), -425);
+
+        let light_level = LightLevel::from_raw(20001);
+        assert!((light_level.lux() - 100.0).abs() < 0.001);
+        assert_eq!(LightLevel::from_lux(100.0), light_level);
+        assert_eq!(LightLevel::from_lux(1.0).raw(), 1);
+        assert_eq!(LightLevel::from_lux(0.5).raw(), 0);
+        assert_eq!(LightLevel::from_raw(0).lux(), 0.0);
+
+        assert_eq!(Battery::from_percent(75).fraction(), 0.75);
+        assert_eq!(Battery::from_percent(120).percent(), 100);
+    }
+
+    #[test]
+    fn validate_state_modifier() {
+        let modifier = StateModifier::new().with_status(1);
+        assert_eq!(modifier.validate("CLIPGenericStatus"), Ok(()));
+        assert_eq!(modifier.validate("CLIPCustom"), Ok(()));
+        assert_eq!(
+            modifier.validate("ZLLPresence"),
+            Err(UnsupportedAttributeError {
+                attribute: "status".into(),
+                type_name: "ZLLPresence".into(),
+            }
+            .into())
+        );
+    }
+
     #[test]
     fn serialize_config_modifier() {
         let modifier = ConfigModifier::new();
@@ -223,12 +1642,69 @@ mod tests {
         let expected_json = json!({});
         assert_eq!(modifier_json, expected_json);
 
-        let modifier = ConfigModifier { on: Some(true) };
+        let modifier = ConfigModifier {
+            on: Some(true),
+            reachable: Some(true),
+            battery: Some(Battery::from_percent(100)),
+            url: Some("url".into()),
+            alert: Some(Alert::None),
+            led_indication: Some(false),
+            user_test: Some(true),
+            sensitivity: Some(2),
+            threshold_dark: Some(LightLevel::from_raw(16000)),
+            threshold_offset: Some(7000),
+            latitude: Some(Coordinate::from_degrees(52.3667)),
+            longitude: Some(Coordinate::from_degrees(-4.9)),
+            sunrise_offset: Some(30),
+            sunset_offset: Some(-30),
+        };
+        let modifier_json = serde_json::to_value(&modifier).unwrap();
+        let expected_json = json!({
+            "on": true,
+            "reachable": true,
+            "battery": 100,
+            "url": "url",
+            "alert": "none",
+            "ledindication": false,
+            "usertest": true,
+            "sensitivity": 2,
+            "tholddark": 16000,
+            "tholdoffset": 7000,
+            "lat": "052.3667N",
+            "long": "004.9000W",
+            "sunriseoffset": 30,
+            "sunsetoffset": -30
+        });
+        assert_eq!(modifier_json, expected_json);
+        assert_eq!(
+            serde_json::from_value::<ConfigModifier>(expected_json).unwrap(),
+            modifier
+        );
+
+        let modifier = ConfigModifier::new().with_threshold_dark(LightLevel::from_lux(1e9));
         let modifier_json = serde_json::to_value(modifier).unwrap();
-        let expected_json = json!({"on": true});
+        let expected_json = json!({"tholddark": 65535});
         assert_eq!(modifier_json, expected_json);
     }
 
+    #[test]
+    fn validate_config_modifier() {
+        let modifier = ConfigModifier::new().with_on(true).with_sensitivity(2);
+        assert_eq!(modifier.validate("ZLLPresence"), Ok(()));
+        assert_eq!(
+            modifier.validate("ZLLSwitch"),
+            Err(UnsupportedAttributeError {
+                attribute: "sensitivity".into(),
+                type_name: "ZLLSwitch".into(),
+            }
+            .into())
+        );
+
+        let modifier = ConfigModifier::new().with_latitude(Coordinate::from_degrees(52.0));
+        assert_eq!(modifier.validate("Daylight"), Ok(()));
+        assert!(modifier.validate("ZLLLightLevel").is_err());
+    }
+
     #[test]
     fn serialize_scanner() {
         let scanner = Scanner::new();
@@ -0,0 +1,573 @@
+use crate::resource::{
+    rule::{Action, ActionRequestMethod, Condition, ConditionOperator, Status},
+    schedule::TimePattern,
+    sensor, Config, Group, Light, Rule, Sensor,
+};
+use chrono::{Datelike, Duration, NaiveDateTime};
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
+
+/// Maximum number of times that rules can trigger each other after a single change.
+const MAX_CASCADE: usize = 32;
+
+/// A change that is applied to the simulator.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    /// Modifies the state of a sensor.
+    SetSensorState {
+        /// Identifier of the sensor.
+        id: String,
+        /// Modifier that is applied to the state.
+        modifier: sensor::StateModifier,
+    },
+    /// Modifies the configuration of a sensor.
+    SetSensorConfig {
+        /// Identifier of the sensor.
+        id: String,
+        /// Modifier that is applied to the configuration.
+        modifier: sensor::ConfigModifier,
+    },
+    /// Advances the clock by the given duration.
+    Advance(Duration),
+}
+
+/// A rule that was triggered by the simulator.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trigger {
+    /// UTC time when the rule was triggered.
+    pub time: NaiveDateTime,
+    /// Identifier of the rule.
+    pub rule_id: String,
+    /// Actions that the rule would issue.
+    pub actions: Vec<Action>,
+}
+
+/// Value of a resource attribute with the time of its last change.
+#[derive(Clone, Debug, PartialEq)]
+struct Attribute {
+    value: JsonValue,
+    changed_at: NaiveDateTime,
+}
+
+/// Local simulator of the rule engine of a bridge.
+///
+/// The simulator keeps a copy of the sensor, light and group attributes that can be used in rule
+/// conditions. Changes to sensors and the clock are applied step by step and the rules that
+/// trigger are reported together with the actions they would issue. Actions that modify sensors,
+/// lights or groups are applied to the simulated attributes, so rules can trigger each other.
+///
+/// The conditions are evaluated like the bridge does:
+///
+/// - `eq`, `lt` and `gt` compare the current value of an attribute.
+/// - `dx` is true when the attribute changed with the current step.
+/// - `ddx` is true when the attribute has not changed for exactly the given duration.
+/// - `stable` and `not stable` check the time since the last change of the attribute.
+/// - `in` and `not in` check the local time of the bridge.
+///
+/// A rule is triggered when all of its conditions are true and either a `dx` or `ddx` condition
+/// fired or the conditions were not all true before. Time is simulated in whole seconds.
+///
+/// # Examples
+///
+/// Check that a rule turns on a light when the switch is pressed:
+/// ```no_run
+/// use huelib::resource::sensor;
+/// use huelib::simulator::{Simulator, Step};
+///
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// let config = bridge.get_config()?;
+/// let mut simulator = Simulator::new(&config, bridge.get_all_rules()?)
+///     .with_sensors(&bridge.get_all_sensors()?)
+///     .with_lights(&bridge.get_all_lights()?)
+///     .with_groups(&bridge.get_all_groups()?);
+/// let triggers = simulator.run(vec![Step::SetSensorState {
+///     id: "5".into(),
+///     modifier: sensor::StateModifier::new().with_button_event(1002),
+/// }]);
+/// for trigger in triggers {
+///     println!("Rule {} issues {:?}", trigger.rule_id, trigger.actions);
+/// }
+/// # Ok::<(), huelib::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Simulator {
+    now: NaiveDateTime,
+    offset: Duration,
+    rules: Vec<Rule>,
+    attributes: HashMap<String, Attribute>,
+    lights: HashMap<String, bool>,
+    groups: HashMap<String, Vec<String>>,
+    results: HashMap<String, bool>,
+    initialized: bool,
+}
+
+impl Simulator {
+    /// Creates a new simulator with the given rules, starting at the current time of the bridge.
+    pub fn new(config: &Config, rules: Vec<Rule>) -> Self {
+        Self {
+            now: config.current_time,
+            offset: Duration::seconds(config.utc_offset().local_minus_utc() as i64),
+            rules,
+            attributes: HashMap::new(),
+            lights: HashMap::new(),
+            groups: HashMap::new(),
+            results: HashMap::new(),
+            initialized: false,
+        }
+    }
+
+    /// Adds the attributes of the given sensors.
+    pub fn with_sensors(mut self, sensors: &[Sensor]) -> Self {
+        for sensor in sensors {
+            let (state, config) = sensor.kind.to_json();
+            let changed_at = sensor.kind.last_updated().unwrap_or(self.now);
+            self.insert_object(&format!("/sensors/{}/state", sensor.id), state, changed_at);
+            self.insert_object(&format!("/sensors/{}/config", sensor.id), config, self.now);
+        }
+        self
+    }
+
+    /// Adds the on state of the given lights.
+    pub fn with_lights(mut self, lights: &[Light]) -> Self {
+        for light in lights {
+            self.lights
+                .insert(light.id.clone(), light.state.on.unwrap_or(false));
+        }
+        self.update_groups();
+        self
+    }
+
+    /// Adds the given groups, so the `any_on` and `all_on` attributes can be used.
+    pub fn with_groups(mut self, groups: &[Group]) -> Self {
+        for group in groups {
+            self.groups.insert(group.id.clone(), group.lights.clone());
+        }
+        self.update_groups();
+        self
+    }
+
+    /// Returns the current UTC time of the simulator.
+    pub fn now(&self) -> NaiveDateTime {
+        self.now
+    }
+
+    /// Returns the current local time of the simulator.
+    pub fn local_time(&self) -> NaiveDateTime {
+        self.now + self.offset
+    }
+
+    /// Returns the value of an attribute, for example `/sensors/1/state/presence`.
+    pub fn attribute(&self, address: &str) -> Option<&JsonValue> {
+        self.attributes.get(address).map(|v| &v.value)
+    }
+
+    /// Applies the steps in order and returns the triggered rules.
+    pub fn run<I: IntoIterator<Item = Step>>(&mut self, steps: I) -> Vec<Trigger> {
+        let mut triggers = Vec::new();
+        for step in steps {
+            triggers.extend(match step {
+                Step::SetSensorState { id, modifier } => self.set_sensor_state(&id, &modifier),
+                Step::SetSensorConfig { id, modifier } => self.set_sensor_config(&id, &modifier),
+                Step::Advance(duration) => self.advance(duration),
+            });
+        }
+        triggers
+    }
+
+    /// Modifies the state of a sensor and returns the triggered rules.
+    pub fn set_sensor_state(&mut self, id: &str, modifier: &sensor::StateModifier) -> Vec<Trigger> {
+        self.initialize();
+        let body = serde_json::to_value(modifier).unwrap_or_default();
+        let events = self.apply(&format!("/sensors/{}/state", id), &body);
+        self.evaluate(events, false)
+    }
+
+    /// Modifies the configuration of a sensor and returns the triggered rules.
+    pub fn set_sensor_config(
+        &mut self,
+        id: &str,
+        modifier: &sensor::ConfigModifier,
+    ) -> Vec<Trigger> {
+        self.initialize();
+        let body = serde_json::to_value(modifier).unwrap_or_default();
+        let events = self.apply(&format!("/sensors/{}/config", id), &body);
+        self.evaluate(events, false)
+    }
+
+    /// Advances the clock and returns the rules that were triggered in the meantime.
+    pub fn advance(&mut self, duration: Duration) -> Vec<Trigger> {
+        self.initialize();
+        let mut triggers = Vec::new();
+        for _ in 0..duration.num_seconds() {
+            self.now += Duration::seconds(1);
+            triggers.extend(self.evaluate(HashSet::new(), true));
+        }
+        triggers
+    }
+
+    fn initialize(&mut self) {
+        if self.initialized {
+            return;
+        }
+        self.initialized = true;
+        for rule in &self.rules {
+            let (result, _) = self.check_rule(rule, &HashSet::new());
+            self.results.insert(rule.id.clone(), result);
+        }
+    }
+
+    fn insert_object(&mut self, prefix: &str, object: JsonValue, changed_at: NaiveDateTime) {
+        if let JsonValue::Object(map) = object {
+            for (key, value) in map {
+                if !value.is_null() {
+                    let address = format!("{}/{}", prefix, key);
+                    self.attributes
+                        .insert(address, Attribute { value, changed_at });
+                }
+            }
+        }
+    }
+
+    /// Sets an attribute and returns whether the value changed.
+    fn set(&mut self, address: String, value: JsonValue) -> bool {
+        let now = self.now;
+        match self.attributes.get_mut(&address) {
+            Some(attribute) if attribute.value == value => false,
+            Some(attribute) => {
+                *attribute = Attribute {
+                    value,
+                    changed_at: now,
+                };
+                true
+            }
+            None => {
+                self.attributes.insert(
+                    address,
+                    Attribute {
+                        value,
+                        changed_at: now,
+                    },
+                );
+                true
+            }
+        }
+    }
+
+    fn update_groups(&mut self) -> HashSet<String> {
+        let mut events = HashSet::new();
+        let mut groups: Vec<_> = self
+            .groups
+            .iter()
+            .map(|(id, lights)| (id.clone(), lights.clone()))
+            .collect();
+        groups.push(("0".to_owned(), self.lights.keys().cloned().collect()));
+        for (id, lights) in groups {
+            let states: Vec<bool> = lights
+                .iter()
+                .filter_map(|v| self.lights.get(v).copied())
+                .collect();
+            let any_on = states.iter().any(|v| *v);
+            let all_on = !states.is_empty() && states.iter().all(|v| *v);
+            for (key, value) in [("any_on", any_on), ("all_on", all_on)] {
+                let address = format!("/groups/{}/state/{}", id, key);
+                if self.set(address.clone(), JsonValue::Bool(value)) {
+                    events.insert(address);
+                }
+            }
+        }
+        events
+    }
+
+    /// Applies the body of a request to the attributes and returns the changed addresses.
+    fn apply(&mut self, address: &str, body: &JsonValue) -> HashSet<String> {
+        let mut events = HashSet::new();
+        let map = match body.as_object() {
+            Some(v) => v,
+            None => return events,
+        };
+        let parts: Vec<&str> = address.trim_start_matches('/').split('/').collect();
+        match parts.as_slice() {
+            ["sensors", _, "state"] | ["sensors", _, "config"] => {
+                for (key, value) in map {
+                    let address = format!("{}/{}", address, key);
+                    if self.set(address.clone(), value.clone()) {
+                        events.insert(address);
+                    }
+                }
+                if address.ends_with("/state") {
+                    let address = format!("{}/lastupdated", address);
+                    let value = JsonValue::String(self.now.format("%Y-%m-%dT%H:%M:%S").to_string());
+                    self.set(address.clone(), value);
+                    events.insert(address);
+                }
+            }
+            ["lights", id, "state"] => {
+                if let Some(on) = map.get("on").and_then(|v| v.as_bool()) {
+                    self.lights.insert((*id).to_owned(), on);
+                    events.extend(self.update_groups());
+                }
+            }
+            ["groups", id, "action"] => {
+                if let Some(on) = map.get("on").and_then(|v| v.as_bool()) {
+                    let lights = match *id {
+                        "0" => self.lights.keys().cloned().collect(),
+                        id => self.groups.get(id).cloned().unwrap_or_default(),
+                    };
+                    for light in lights {
+                        self.lights.insert(light, on);
+                    }
+                    events.extend(self.update_groups());
+                }
+            }
+            _ => {}
+        }
+        events
+    }
+
+    fn evaluate(&mut self, mut events: HashSet<String>, mut tick: bool) -> Vec<Trigger> {
+        let mut triggers = Vec::new();
+        for _ in 0..MAX_CASCADE {
+            let mut fired = Vec::new();
+            for rule in &self.rules {
+                if rule.status != Status::Enabled {
+                    continue;
+                }
+                let relevant = rule
+                    .conditions
+                    .iter()
+                    .any(|v| events.contains(&v.address) || (tick && is_time_based(&v.operator)));
+                if !relevant {
+                    continue;
+                }
+                let (result, event) = self.check_rule(rule, &events);
+                let previous = self.results.insert(rule.id.clone(), result);
+                if result && (event || previous == Some(false)) {
+                    fired.push(rule.clone());
+                }
+            }
+            if fired.is_empty() {
+                break;
+            }
+            events = HashSet::new();
+            tick = false;
+            for rule in fired {
+                for action in &rule.actions {
+                    if action.request_method == ActionRequestMethod::Put {
+                        events.extend(self.apply(&action.address, &action.body));
+                    }
+                }
+                triggers.push(Trigger {
+                    time: self.now,
+                    rule_id: rule.id,
+                    actions: rule.actions,
+                });
+            }
+        }
+        triggers
+    }
+
+    /// Returns whether all conditions of the rule are true and whether an event condition fired.
+    fn check_rule(&self, rule: &Rule, events: &HashSet<String>) -> (bool, bool) {
+        let mut event = false;
+        for condition in &rule.conditions {
+            match self.check_condition(condition, events) {
+                Some(v) => event |= v,
+                None => return (false, false),
+            }
+        }
+        (!rule.conditions.is_empty(), event)
+    }
+
+    /// Returns `None` if the condition is false, otherwise whether it is an event that fired.
+    fn check_condition(&self, condition: &Condition, events: &HashSet<String>) -> Option<bool> {
+        use ConditionOperator::*;
+        let value = condition.value.as_deref();
+        if condition.address == "/config/localtime" {
+            let local_time = self.local_time();
+            let inside = match value.and_then(|v| v.parse().ok()) {
+                Some(TimePattern::Interval {
+                    weekdays,
+                    start,
+                    end,
+                }) => {
+                    let time = local_time.time();
+                    let (inside, date) = if start <= end {
+                        (time >= start && time < end, local_time.date())
+                    } else if time >= start {
+                        (true, local_time.date())
+                    } else {
+                        (time < end, local_time.date() - Duration::days(1))
+                    };
+                    inside && weekdays.is_none_or(|v| v.contains(date.weekday()))
+                }
+                _ => return None,
+            };
+            return match (&condition.operator, inside) {
+                (In, true) | (NotIn, false) => Some(false),
+                _ => None,
+            };
+        }
+        let attribute = self.attributes.get(&condition.address)?;
+        let duration = || match value.and_then(|v| v.parse().ok()) {
+            Some(TimePattern::Timer { duration, .. }) => Some(duration),
+            _ => None,
+        };
+        let since_change = self.now - attribute.changed_at;
+        let result = match condition.operator {
+            Equals => compare(&attribute.value, value?)? == std::cmp::Ordering::Equal,
+            LessThan => compare(&attribute.value, value?)? == std::cmp::Ordering::Less,
+            GreaterThan => compare(&attribute.value, value?)? == std::cmp::Ordering::Greater,
+            Dx => return events.contains(&condition.address).then_some(true),
+            Ddx => return (since_change == duration()?).then_some(true),
+            Stable => since_change >= duration()?,
+            NotStable => since_change < duration()?,
+            In | NotIn => false,
+        };
+        result.then_some(false)
+    }
+}
+
+fn is_time_based(operator: &ConditionOperator) -> bool {
+    use ConditionOperator::*;
+    matches!(operator, Ddx | Stable | NotStable | In | NotIn)
+}
+
+/// Compares an attribute value with the value of a condition.
+fn compare(attribute: &JsonValue, value: &str) -> Option<std::cmp::Ordering> {
+    match attribute {
+        JsonValue::Bool(v) => Some(v.cmp(&value.parse().ok()?)),
+        JsonValue::Number(v) => v.as_f64()?.partial_cmp(&value.parse().ok()?),
+        JsonValue::String(v) => Some(v.as_str().cmp(value)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::rule;
+    use crate::resource::rule::Condition;
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    fn condition(address: &str, operator: &str, value: Option<&str>) -> Condition {
+        serde_json::from_value(json!({
+            "address": address,
+            "operator": operator,
+            "value": value
+        }))
+        .unwrap()
+    }
+
+    fn simulator(rules: Vec<Rule>) -> Simulator {
+        let now = NaiveDate::from_ymd_opt(2020, 1, 1)
+            .unwrap()
+            .and_hms_opt(22, 0, 0)
+            .unwrap();
+        let sensor: Sensor = serde_json::from_value(json!({
+            "name": "Motion sensor",
+            "type": "ZLLPresence",
+            "modelid": "SML001",
+            "state": {"presence": false, "lastupdated": "2020-01-01T21:00:00"},
+            "config": {"on": true, "reachable": true, "battery": 100}
+        }))
+        .unwrap();
+        let mut simulator = Simulator {
+            now,
+            offset: Duration::hours(1),
+            rules,
+            attributes: HashMap::new(),
+            lights: HashMap::new(),
+            groups: HashMap::new(),
+            results: HashMap::new(),
+            initialized: false,
+        }
+        .with_sensors(&[sensor.with_id("2".into())]);
+        simulator.groups.insert("1".into(), vec!["1".into()]);
+        simulator.lights.insert("1".into(), false);
+        simulator.update_groups();
+        simulator
+    }
+
+    #[test]
+    fn motion_rules() {
+        let presence = "/sensors/2/state/presence";
+        let rules = vec![
+            rule(
+                "1",
+                vec![
+                    condition(presence, "eq", Some("true")),
+                    condition(presence, "dx", None),
+                    condition("/config/localtime", "in", Some("T20:00:00/T08:00:00")),
+                ],
+                json!({"on": true}),
+            ),
+            rule(
+                "2",
+                vec![
+                    condition(presence, "eq", Some("false")),
+                    condition(presence, "ddx", Some("PT00:05:00")),
+                    condition("/groups/1/state/any_on", "eq", Some("true")),
+                ],
+                json!({"on": false}),
+            ),
+            rule(
+                "3",
+                vec![condition("/groups/1/state/all_on", "dx", None)],
+                json!({}),
+            ),
+        ];
+        let mut simulator = simulator(rules);
+        let motion = |presence| Step::SetSensorState {
+            id: "2".into(),
+            modifier: sensor::StateModifier::new().with_presence(presence),
+        };
+
+        let triggers = simulator.run(vec![motion(true)]);
+        let ids: Vec<_> = triggers.iter().map(|v| v.rule_id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+        assert_eq!(
+            simulator.attribute("/groups/1/state/any_on"),
+            Some(&json!(true))
+        );
+
+        let triggers = simulator.run(vec![motion(false), Step::Advance(Duration::minutes(4))]);
+        assert!(triggers.is_empty());
+
+        let triggers = simulator.run(vec![Step::Advance(Duration::minutes(2))]);
+        let ids: Vec<_> = triggers.iter().map(|v| v.rule_id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+        assert_eq!(
+            triggers[0].time,
+            NaiveDate::from_ymd_opt(2020, 1, 1)
+                .unwrap()
+                .and_hms_opt(22, 5, 0)
+                .unwrap()
+        );
+
+        let triggers = simulator.run(vec![Step::Advance(Duration::hours(1))]);
+        assert!(triggers.is_empty());
+    }
+
+    #[test]
+    fn stable_triggers_once() {
+        let presence = "/sensors/2/state/presence";
+        let rules = vec![rule(
+            "1",
+            vec![
+                condition(presence, "eq", Some("false")),
+                condition(presence, "stable", Some("PT00:01:00")),
+            ],
+            json!({"on": false}),
+        )];
+        let mut simulator = simulator(rules);
+        let triggers =
+            simulator.set_sensor_state("2", &sensor::StateModifier::new().with_presence(true));
+        assert!(triggers.is_empty());
+        simulator.set_sensor_state("2", &sensor::StateModifier::new().with_presence(false));
+        assert!(simulator.advance(Duration::seconds(59)).is_empty());
+        assert_eq!(simulator.advance(Duration::minutes(5)).len(), 1);
+    }
+}
@@ -1,5 +1,8 @@
+use crate::resource::Adjust;
 use chrono::{NaiveDateTime, NaiveTime};
 use serde::de::{Deserialize, Deserializer, Error};
+use std::convert::TryInto;
+use std::ops::Neg;
 
 pub(crate) fn deserialize_option_string<'de, D: Deserializer<'de>>(
     deserializer: D,
@@ -33,6 +36,45 @@ pub(crate) fn deserialize_option_time<'de, D: Deserializer<'de>>(
     })
 }
 
+/// Converts the override and increment values of an attribute into an [`Adjust`].
+pub(crate) fn to_adjust<T, I>(value: Option<T>, increment: Option<I>) -> Option<Adjust<T>>
+where
+    I: Default + PartialOrd + Neg<Output = I> + TryInto<T>,
+{
+    match (value, increment) {
+        (Some(v), _) => Some(Adjust::Override(v)),
+        (None, Some(v)) if v < I::default() => (-v).try_into().ok().map(Adjust::Decrement),
+        (None, Some(v)) => v.try_into().ok().map(Adjust::Increment),
+        (None, None) => None,
+    }
+}
+
+/// Converts the override and increment values of color space coordinates into an [`Adjust`].
+pub(crate) fn to_adjust_tuple(
+    value: Option<(f32, f32)>,
+    increment: Option<(f32, f32)>,
+) -> Option<Adjust<(f32, f32)>> {
+    match (value, increment) {
+        (Some(v), _) => Some(Adjust::Override(v)),
+        (None, Some((x, y))) if x <= 0.0 && y <= 0.0 && (x, y) != (0.0, 0.0) => {
+            Some(Adjust::Decrement((-x, -y)))
+        }
+        (None, Some(v)) => Some(Adjust::Increment(v)),
+        (None, None) => None,
+    }
+}
+
+/// Compares identifiers numerically if possible, so that `"2"` is ordered before `"10"`.
+///
+/// Numeric identifiers are ordered before all other identifiers, which keeps the order total.
+pub(crate) fn compare_ids(a: &str, b: &str) -> std::cmp::Ordering {
+    fn key(id: &str) -> (bool, Option<u64>, &str) {
+        let number = id.parse().ok();
+        (number.is_none(), number, id)
+    }
+    key(a).cmp(&key(b))
+}
+
 macro_rules! custom_serialize {
     ($serializer:expr, $struct_name:expr; $($k:ident => ($($v:tt)*),)*) => {
         let mut len = 0;
@@ -80,6 +122,14 @@ mod tests {
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
     use serde_json::json;
 
+    #[test]
+    fn sort_ids() {
+        let mut ids = vec!["10", "a0", "9", "b", "2", "a0", "10"];
+        ids.sort_by(|a, b| super::compare_ids(a, b));
+        ids.dedup();
+        assert_eq!(ids, vec!["2", "9", "10", "a0", "b"]);
+    }
+
     #[test]
     fn deserialize_option_string() {
         let json = json!("none");
@@ -107,8 +157,8 @@ mod tests {
 
         let json = json!("2020-01-01T01:30:00");
         let value = super::deserialize_option_date_time(json).unwrap();
-        let date = NaiveDate::from_ymd(2020, 1, 1);
-        let time = NaiveTime::from_hms(1, 30, 0);
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let time = NaiveTime::from_hms_opt(1, 30, 0).unwrap();
         assert_eq!(value, Some(NaiveDateTime::new(date, time)));
     }
 
@@ -124,6 +174,6 @@ mod tests {
 
         let json = json!("T02:00:20");
         let value = super::deserialize_option_time(json).unwrap();
-        assert_eq!(value, Some(NaiveTime::from_hms(2, 0, 20)));
+        assert_eq!(value, Some(NaiveTime::from_hms_opt(2, 0, 20).unwrap()));
     }
 }